@@ -0,0 +1,2 @@
+-- O preenchimento de data_typesafe não é desfeito: a coluna continua válida.
+DROP INDEX IF EXISTS idx_mega_sena_data_typesafe;
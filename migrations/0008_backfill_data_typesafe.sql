@@ -0,0 +1,6 @@
+UPDATE t_mega_sena
+SET data_typesafe = substr(data, 7, 4) || '-' || substr(data, 4, 2) || '-' || substr(data, 1, 2)
+WHERE data_typesafe IS NULL
+  AND data GLOB '[0-9][0-9]/[0-9][0-9]/[0-9][0-9][0-9][0-9]';
+
+CREATE INDEX IF NOT EXISTS idx_mega_sena_data_typesafe ON t_mega_sena(data_typesafe);
@@ -0,0 +1,21 @@
+-- Devolve as apostas de 6 números para t_mega_sena, como eram gravadas antes,
+-- usando o UUID do lote no lugar do concurso.
+INSERT INTO t_mega_sena (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6,
+                         generated_by_rust, inserted_at)
+SELECT l.uuid,
+       strftime('%d/%m/%Y', a.created_at),
+       date(a.created_at),
+       CAST(substr(a.numeros, 1, 2) AS INTEGER),
+       CAST(substr(a.numeros, 4, 2) AS INTEGER),
+       CAST(substr(a.numeros, 7, 2) AS INTEGER),
+       CAST(substr(a.numeros, 10, 2) AS INTEGER),
+       CAST(substr(a.numeros, 13, 2) AS INTEGER),
+       CAST(substr(a.numeros, 16, 2) AS INTEGER),
+       1,
+       a.created_at
+FROM t_aposta a
+         JOIN t_lote l ON l.id = a.lote_id
+WHERE length(a.numeros) = 17;
+
+DROP TABLE IF EXISTS t_aposta;
+DROP TABLE IF EXISTS t_lote;
@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS t_lote (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid       TEXT NOT NULL UNIQUE,
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS t_aposta (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    lote_id    INTEGER NOT NULL REFERENCES t_lote (id),
+    numeros    TEXT NOT NULL,
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_aposta_lote_id ON t_aposta (lote_id);
+
+-- Jogos gerados eram gravados em t_mega_sena com um UUID no lugar do concurso.
+-- Cada dia de geração vira um lote. Sem data_typesafe (data fora do formato
+-- dd/mm/aaaa), vale o dia da gravação; sem nenhum dos dois, o lote fica sem
+-- data, e o IS do JOIN abaixo casa esses NULLs para nenhum jogo se perder.
+INSERT INTO t_lote (uuid, created_at)
+SELECT lower(hex(randomblob(16))), COALESCE(data_typesafe, date(inserted_at))
+FROM t_mega_sena
+WHERE generated_by_rust = 1
+GROUP BY COALESCE(data_typesafe, date(inserted_at));
+
+INSERT INTO t_aposta (lote_id, numeros, created_at)
+SELECT l.id,
+       printf('%02d,%02d,%02d,%02d,%02d,%02d', m.bola_1, m.bola_2, m.bola_3, m.bola_4, m.bola_5, m.bola_6),
+       m.inserted_at
+FROM t_mega_sena m
+         JOIN t_lote l ON l.created_at IS COALESCE(m.data_typesafe, date(m.inserted_at))
+WHERE m.generated_by_rust = 1;
+
+DELETE FROM t_mega_sena WHERE generated_by_rust = 1;
@@ -0,0 +1,3 @@
+ALTER TABLE t_mega_sena DROP COLUMN rateio_quadra_centavos;
+ALTER TABLE t_mega_sena DROP COLUMN rateio_quina_centavos;
+ALTER TABLE t_mega_sena DROP COLUMN rateio_sena_centavos;
@@ -0,0 +1,5 @@
+-- Valor pago por ganhador em cada faixa, em centavos. NULL quando o CSV
+-- importado não traz a premiação do concurso.
+ALTER TABLE t_mega_sena ADD COLUMN rateio_sena_centavos INTEGER;
+ALTER TABLE t_mega_sena ADD COLUMN rateio_quina_centavos INTEGER;
+ALTER TABLE t_mega_sena ADD COLUMN rateio_quadra_centavos INTEGER;
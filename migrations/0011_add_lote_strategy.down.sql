@@ -0,0 +1,2 @@
+ALTER TABLE t_lote DROP COLUMN config;
+ALTER TABLE t_lote DROP COLUMN strategy;
@@ -0,0 +1,6 @@
+-- Estratégia de sorteio e configuração (JSON) usadas para gerar cada lote.
+ALTER TABLE t_lote ADD COLUMN strategy TEXT;
+ALTER TABLE t_lote ADD COLUMN config TEXT;
+
+-- Até aqui todo lote foi gerado com sorteio uniforme.
+UPDATE t_lote SET strategy = 'uniforme';
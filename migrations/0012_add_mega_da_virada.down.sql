@@ -0,0 +1,3 @@
+ALTER TABLE t_lote DROP COLUMN virada;
+ALTER TABLE t_mega_sena DROP COLUMN acumulado_virada_centavos;
+ALTER TABLE t_mega_sena DROP COLUMN acumulado_sena_centavos;
@@ -0,0 +1,8 @@
+-- Valores acumulados informados pela Caixa em cada concurso, em centavos: o
+-- prêmio principal que passa ao concurso seguinte e a reserva do sorteio
+-- especial da Mega da Virada. NULL quando o CSV importado não traz.
+ALTER TABLE t_mega_sena ADD COLUMN acumulado_sena_centavos INTEGER;
+ALTER TABLE t_mega_sena ADD COLUMN acumulado_virada_centavos INTEGER;
+
+-- Ano da Mega da Virada para a qual o lote foi gerado.
+ALTER TABLE t_lote ADD COLUMN virada INTEGER;
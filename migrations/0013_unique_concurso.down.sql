@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_mega_sena_concurso;
@@ -0,0 +1,19 @@
+-- Reimportar o mesmo CSV duplicava os concursos. Fica a primeira linha de
+-- cada concurso, completada com os valores em reais das repetidas.
+UPDATE t_mega_sena AS m
+SET rateio_sena_centavos = COALESCE(m.rateio_sena_centavos,
+        (SELECT MAX(d.rateio_sena_centavos) FROM t_mega_sena d WHERE d.concurso = m.concurso)),
+    rateio_quina_centavos = COALESCE(m.rateio_quina_centavos,
+        (SELECT MAX(d.rateio_quina_centavos) FROM t_mega_sena d WHERE d.concurso = m.concurso)),
+    rateio_quadra_centavos = COALESCE(m.rateio_quadra_centavos,
+        (SELECT MAX(d.rateio_quadra_centavos) FROM t_mega_sena d WHERE d.concurso = m.concurso)),
+    acumulado_sena_centavos = COALESCE(m.acumulado_sena_centavos,
+        (SELECT MAX(d.acumulado_sena_centavos) FROM t_mega_sena d WHERE d.concurso = m.concurso)),
+    acumulado_virada_centavos = COALESCE(m.acumulado_virada_centavos,
+        (SELECT MAX(d.acumulado_virada_centavos) FROM t_mega_sena d WHERE d.concurso = m.concurso))
+WHERE m.id IN (SELECT MIN(id) FROM t_mega_sena GROUP BY concurso HAVING COUNT(*) > 1);
+
+DELETE FROM t_mega_sena
+WHERE id NOT IN (SELECT MIN(id) FROM t_mega_sena GROUP BY concurso);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_mega_sena_concurso ON t_mega_sena(concurso);
@@ -0,0 +1,309 @@
+//! Rotas da API HTTP do comando `serve`, independentes do servidor: cada
+//! requisição vira uma [`Resposta`] JSON a partir do [`Store`].
+//!
+//! | Rota | Descrição |
+//! |---|---|
+//! | `GET /health` | Verifica se o servidor está no ar. |
+//! | `GET /stats` | [`Estatisticas`](crate::Estatisticas) do período (`?de=&ate=`). |
+//! | `POST /generate` | Gera (e grava) um lote; corpo [`PedidoGeracao`]. |
+//! | `GET /lotes` | Lotes criados no período (`?de=&ate=`). |
+//! | `GET /lotes/{id}` | Um lote gravado. |
+//! | `GET /check` | Confere `?numeros=1,2,3,4,5,6` contra o período. |
+//! | `POST /import` | Importa o CSV enviado no corpo. |
+//! | `GET /virada` | [`PanoramaVirada`](crate::PanoramaVirada) visto de hoje. |
+
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use chrono::Local;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::core::mega_sena::Bet;
+use crate::core::periodo::Periodo;
+use crate::database::lotes::SelecaoLotes;
+use crate::engine::conferencia::Rateio;
+use crate::engine::mega_sena_service::GeneratorConfig;
+use crate::engine::otimizador::{Objetivo, Otimizado};
+use crate::engine::virada::Contagem;
+use crate::error::{Error, Result};
+use crate::shared::date;
+use crate::store::Store;
+
+/// Resposta de uma rota: status HTTP e corpo JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resposta {
+    pub status: u16,
+    pub corpo: serde_json::Value,
+}
+
+impl Resposta {
+    fn ok(corpo: impl Serialize) -> Result<Self> {
+        Ok(Self {
+            status: 200,
+            corpo: serde_json::to_value(corpo)?,
+        })
+    }
+
+    fn erro(status: u16, mensagem: impl Into<String>) -> Self {
+        Self {
+            status,
+            corpo: json!({ "erro": mensagem.into() }),
+        }
+    }
+}
+
+impl From<Error> for Resposta {
+    fn from(erro: Error) -> Self {
+        let status = match erro {
+            Error::Validation(_) | Error::Config(_) | Error::Import { .. } => 400,
+            Error::Generation(_) => 422,
+            Error::Database(_) | Error::Migration { .. } | Error::Io(_) | Error::Json(_) => 500,
+        };
+        Self {
+            status,
+            corpo: json!({ "erro": erro.to_string(), "codigo": erro.exit_code() }),
+        }
+    }
+}
+
+/// Maior lote que a API gera por requisição.
+pub const MAX_JOGOS: usize = 1_000;
+/// Teto de candidatos sorteados por geração.
+pub const MAX_TENTATIVAS: u64 = 10_000_000;
+/// Teto de tempo da geração e, à parte, da otimização: o servidor atende
+/// uma requisição por vez e não pode ficar preso em filtros impossíveis.
+pub const TEMPO_GERACAO: Duration = Duration::from_secs(10);
+/// Teto de [`Otimizacao::iteracoes`](crate::engine::otimizador::Otimizacao::iteracoes).
+pub const MAX_ITERACOES: u64 = 1_000_000;
+/// Teto de [`Otimizacao::lotes_aleatorios`](crate::engine::otimizador::Otimizacao::lotes_aleatorios).
+pub const MAX_LOTES_ALEATORIOS: usize = 100;
+
+/// Corpo de `POST /generate`; todos os campos são opcionais. A configuração
+/// passa pelos tetos da API (`MAX_JOGOS`, `TEMPO_GERACAO`...) antes de gerar.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PedidoGeracao {
+    /// Sem configuração, usa a padrão com as flags do banco.
+    pub config: Option<GeneratorConfig>,
+    pub periodo: Periodo,
+    /// Semente para repetir o lote.
+    pub seed: Option<u64>,
+    /// Gera sem gravar o lote.
+    pub simular: bool,
+    /// Marca o lote gravado para a próxima Mega da Virada.
+    pub virada: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct LoteGerado {
+    lote_id: Option<i64>,
+    /// Ano da Mega da Virada para a qual o lote foi marcado.
+    virada: Option<i32>,
+    seed: u64,
+    config: GeneratorConfig,
+    jogos: Vec<Bet>,
+    /// Resultado da busca, quando a configuração pede otimização.
+    otimizacao: Option<ResumoOtimizacao>,
+}
+
+/// [`Otimizado`] sem os jogos, que já vão em [`LoteGerado`].
+#[derive(Debug, Serialize)]
+struct ResumoOtimizacao {
+    objetivo: Objetivo,
+    valor: f64,
+    inicial: f64,
+    aleatorio: Option<f64>,
+    iteracoes: u64,
+    aceitas: u64,
+}
+
+/// Atende uma requisição. `caminho` pode trazer a query string.
+pub fn atender(store: &mut Store, metodo: &str, caminho: &str, corpo: &[u8]) -> Resposta {
+    let (rota, query) = caminho.split_once('?').unwrap_or((caminho, ""));
+    let query = parse_query(query);
+    let segmentos: Vec<&str> = rota.trim_matches('/').split('/').collect();
+
+    let resposta = match (metodo, segmentos.as_slice()) {
+        ("GET", ["health"]) => Resposta::ok(json!({ "status": "ok" })),
+        ("GET", ["stats"]) => periodo(&query)
+            .and_then(|p| store.stats(&p))
+            .and_then(Resposta::ok),
+        ("POST", ["generate"]) => generate(store, corpo),
+        ("GET", ["lotes"]) => periodo(&query)
+            .and_then(|p| store.lotes(&SelecaoLotes::Periodo(p)))
+            .and_then(Resposta::ok),
+        ("GET", ["lotes", id]) => match id.parse() {
+            Ok(id) => match store.lotes(&SelecaoLotes::Ids(vec![id])) {
+                Ok(lotes) => Resposta::ok(&lotes[0]),
+                Err(Error::Config(motivo)) => Ok(Resposta::erro(404, motivo)),
+                Err(e) => Err(e),
+            },
+            Err(_) => Ok(Resposta::erro(400, format!("id de lote inválido: {}", id))),
+        },
+        ("GET", ["check"]) => check(store, &query),
+        ("POST", ["import"]) => import(store, corpo),
+        ("GET", ["virada"]) => store
+            .virada(Local::now().date_naive())
+            .and_then(Resposta::ok),
+        (_, ["health" | "stats" | "generate" | "lotes" | "check" | "import" | "virada"])
+        | (_, ["lotes", _]) => Ok(Resposta::erro(
+            405,
+            format!("método {} não permitido em {}", metodo, rota),
+        )),
+        _ => Ok(Resposta::erro(
+            404,
+            format!("rota não encontrada: {}", rota),
+        )),
+    };
+
+    resposta.unwrap_or_else(Resposta::from)
+}
+
+fn generate(store: &mut Store, corpo: &[u8]) -> Result<Resposta> {
+    let pedido: PedidoGeracao = if corpo.iter().all(u8::is_ascii_whitespace) {
+        PedidoGeracao::default()
+    } else {
+        serde_json::from_slice(corpo)
+            .map_err(|e| Error::Config(format!("corpo de /generate inválido: {}", e)))?
+    };
+
+    let config = limitar(match pedido.config {
+        Some(config) => config,
+        None => store.generator_config()?,
+    });
+    let seed = pedido.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (jogos, otimizacao) = if config.otimizacao.is_some() {
+        let Otimizado {
+            jogos,
+            objetivo,
+            valor,
+            inicial,
+            aleatorio,
+            iteracoes,
+            aceitas,
+        } = store.optimize(&pedido.periodo, &config, &mut rng, |_| {})?;
+        let resumo = ResumoOtimizacao {
+            objetivo,
+            valor,
+            inicial,
+            aleatorio,
+            iteracoes,
+            aceitas,
+        };
+        (jogos, Some(resumo))
+    } else {
+        (
+            store.generate(&pedido.periodo, &config, &mut rng, |_| {})?,
+            None,
+        )
+    };
+    let virada = (pedido.virada && !pedido.simular)
+        .then(|| Contagem::a_partir_de(Local::now().date_naive()).ano);
+    let lote_id = if pedido.simular {
+        None
+    } else {
+        Some(store.save_batch(&jogos, &config, virada)?)
+    };
+
+    Resposta::ok(LoteGerado {
+        lote_id,
+        virada,
+        seed,
+        config,
+        jogos,
+        otimizacao,
+    })
+}
+
+/// Aplica os tetos da API à configuração vinda do cliente; o lote devolvido
+/// traz a configuração já limitada.
+fn limitar(mut config: GeneratorConfig) -> GeneratorConfig {
+    config.quantidade = config.quantidade.min(MAX_JOGOS);
+    config.orcamento.max_tentativas = Some(teto(config.orcamento.max_tentativas, MAX_TENTATIVAS));
+    config.orcamento.tempo_limite = Some(teto(config.orcamento.tempo_limite, TEMPO_GERACAO));
+    if let Some(otimizacao) = &mut config.otimizacao {
+        otimizacao.iteracoes = otimizacao.iteracoes.min(MAX_ITERACOES);
+        otimizacao.lotes_aleatorios = otimizacao.lotes_aleatorios.min(MAX_LOTES_ALEATORIOS);
+        otimizacao.tempo_limite = Some(teto(otimizacao.tempo_limite, TEMPO_GERACAO));
+    }
+    config
+}
+
+/// O valor informado, sem passar do teto; ausente, o próprio teto.
+fn teto<T: Ord + Copy>(valor: Option<T>, teto: T) -> T {
+    valor.map_or(teto, |v| v.min(teto))
+}
+
+fn check(store: &Store, query: &HashMap<String, String>) -> Result<Resposta> {
+    let numeros = query
+        .get("numeros")
+        .ok_or_else(|| Error::Config("informe ?numeros=1,2,3,4,5,6".to_string()))?;
+    let aposta: Bet = numeros.parse()?;
+    let conferencia = store.conferir(&aposta, &periodo(query)?, &Rateio::default())?;
+    Resposta::ok(conferencia)
+}
+
+fn import(store: &mut Store, corpo: &[u8]) -> Result<Resposta> {
+    // A importação lê de um arquivo; o upload passa por um temporário.
+    let caminho = std::env::temp_dir().join(format!("mega_sena_upload_{}.csv", Uuid::new_v4()));
+    fs::write(&caminho, corpo)?;
+    let importados = store.import_csv(&caminho);
+    fs::remove_file(&caminho)?;
+    Resposta::ok(json!({ "importados": importados? }))
+}
+
+fn periodo(query: &HashMap<String, String>) -> Result<Periodo> {
+    let data = |chave: &str| {
+        query
+            .get(chave)
+            .map(|valor| {
+                date::parse_data(valor).map_err(|e| Error::Config(format!("{}: {}", chave, e)))
+            })
+            .transpose()
+    };
+    Ok(Periodo::new(data("de")?, data("ate")?))
+}
+
+/// Pares `chave=valor` da query string, com `%XX` e `+` decodificados.
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|par| !par.is_empty())
+        .map(|par| {
+            let (chave, valor) = par.split_once('=').unwrap_or((par, ""));
+            (decodificar(chave), decodificar(valor))
+        })
+        .collect()
+}
+
+fn decodificar(texto: &str) -> String {
+    let bytes = texto.as_bytes();
+    let mut saida = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => saida.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|h| std::str::from_utf8(h).ok());
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        saida.push(byte);
+                        i += 2;
+                    }
+                    None => saida.push(b'%'),
+                }
+            }
+            byte => saida.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&saida).into_owned()
+}
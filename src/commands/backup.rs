@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use rusqlite::Connection;
+
+use mega_da_virada_generator::Result;
+use mega_da_virada_generator::database::snapshot::{self, SnapshotPolicy};
+use mega_da_virada_generator::database::sqlite;
+
+use crate::commands::{Contexto, confirmar};
+
+#[derive(Debug, Args)]
+pub struct BackupArgs {
+    /// Arquivo de destino. Sem ele, grava um snapshot no diretório de
+    /// snapshots aplicando a retenção.
+    #[arg(long)]
+    pub to: Option<PathBuf>,
+
+    /// Lista os snapshots existentes em vez de gravar um novo.
+    #[arg(long, conflicts_with = "to")]
+    pub list: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RestoreArgs {
+    /// Backup ou snapshot a restaurar.
+    pub from: PathBuf,
+
+    /// Não pede confirmação.
+    #[arg(long)]
+    pub yes: bool,
+}
+
+pub fn backup(ctx: &Contexto, args: BackupArgs) -> Result<()> {
+    let conn = Connection::open(&ctx.db)?;
+    let policy = ctx.snapshot_policy();
+
+    if args.list {
+        let snapshots = snapshot::list_for(&conn, &policy.dir)?;
+        if snapshots.is_empty() {
+            println!("Nenhum snapshot em {}", policy.dir.display());
+        }
+        for path in snapshots {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    let destino = match args.to {
+        Some(destino) => {
+            sqlite::backup_to_file(&conn, &destino)?;
+            destino
+        }
+        None => snapshot::take(&conn, &policy, "manual")?,
+    };
+    println!("Backup gravado em {}", destino.display());
+    Ok(())
+}
+
+pub fn restore(ctx: &Contexto, args: RestoreArgs) -> Result<()> {
+    let mut conn = Connection::open(&ctx.db)?;
+
+    if !args.yes
+        && !confirmar(&format!(
+            "Substituir {} pelo conteúdo de {}?",
+            ctx.db.display(),
+            args.from.display()
+        ))?
+    {
+        println!("Nada foi alterado.");
+        return Ok(());
+    }
+
+    if let Some(policy) = &ctx.store_options.snapshots {
+        let path = snapshot::take(&conn, policy, "pre-restore")?;
+        println!("Snapshot do estado atual: {}", path.display());
+    }
+
+    sqlite::restore_from_file(&mut conn, &args.from)?;
+    println!("Banco restaurado a partir de {}", args.from.display());
+    Ok(())
+}
+
+impl Contexto {
+    /// Política configurada ou, com `--no-snapshot`, a padrão ao lado do banco
+    /// (para `backup` e `backup --list` continuarem funcionando).
+    fn snapshot_policy(&self) -> SnapshotPolicy {
+        self.store_options
+            .snapshots
+            .clone()
+            .unwrap_or_else(|| SnapshotPolicy::beside(&self.db, usize::MAX))
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use clap::Args;
+
+use mega_da_virada_generator::shared::{date, dinheiro};
+use mega_da_virada_generator::{Bet, Conferencia, Periodo, Rateio, Result};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// De 6 a 20 números, separados por espaço, vírgula, ponto e vírgula ou hífen.
+    #[arg(required = true, num_args = 1..)]
+    pub numeros: Vec<String>,
+
+    /// Valor da sena por ganhador para concursos sem rateio importado (ex.: 1.000.000,00).
+    #[arg(long, value_parser = parse_valor)]
+    pub valor_sena: Option<u64>,
+
+    /// Valor da quina por ganhador para concursos sem rateio importado.
+    #[arg(long, value_parser = parse_valor)]
+    pub valor_quina: Option<u64>,
+
+    /// Valor da quadra por ganhador para concursos sem rateio importado.
+    #[arg(long, value_parser = parse_valor)]
+    pub valor_quadra: Option<u64>,
+
+    /// Imprime o resultado em JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Considera apenas concursos a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Considera apenas concursos até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+}
+
+fn parse_valor(valor: &str) -> std::result::Result<u64, String> {
+    dinheiro::parse_reais(valor).ok_or_else(|| format!("valor inválido: {}", valor))
+}
+
+pub fn run(ctx: &Contexto, args: CheckArgs) -> Result<()> {
+    let aposta: Bet = args.numeros.join(" ").parse()?;
+    let periodo = Periodo::new(args.de, args.ate);
+    let padrao = Rateio {
+        sena: args.valor_sena,
+        quina: args.valor_quina,
+        quadra: args.valor_quadra,
+    };
+
+    let store = ctx.open_store()?;
+    let conferencia = store.conferir(&aposta, &periodo, &padrao)?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&conferencia)?;
+        println!("{}", json);
+    } else {
+        print_conferencia(&conferencia);
+    }
+
+    Ok(())
+}
+
+fn print_conferencia(conferencia: &Conferencia) {
+    println!(
+        "Jogo {} conferido contra {} concursos",
+        conferencia.aposta, conferencia.concursos_conferidos
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let exatos: Vec<String> = conferencia
+        .exatos()
+        .map(|p| p.concurso.to_string())
+        .collect();
+    if exatos.is_empty() {
+        println!("Jogo inédito! Nenhum concurso com as seis dezenas.");
+    } else {
+        println!("Já saiu:");
+        for concurso in exatos {
+            println!("• {}", concurso);
+        }
+    }
+
+    if !conferencia.premiacoes.is_empty() {
+        println!("\nPremiações:");
+    }
+    for premiacao in &conferencia.premiacoes {
+        let premios: Vec<String> = premiacao
+            .premios
+            .iter()
+            .map(|(faixa, qtd)| format!("{}x {}", qtd, faixa))
+            .collect();
+        let valor = premiacao
+            .valor
+            .map(dinheiro::format_reais)
+            .unwrap_or_else(|| "valor desconhecido".to_string());
+        println!(
+            "• Concurso {} ({}): {} — {}",
+            premiacao.concurso.concurso,
+            date::format_data_br(premiacao.concurso.data),
+            premios.join(", "),
+            valor
+        );
+    }
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for (faixa, qtd) in &conferencia.totais {
+        println!("{:<7} {}", format!("{}:", faixa), qtd);
+    }
+    println!("Total:  {}", dinheiro::format_reais(conferencia.total));
+    if conferencia.sem_valor > 0 {
+        println!(
+            "⚠️  {} premiações sem rateio importado ficaram fora do total; \
+             informe --valor-sena, --valor-quina ou --valor-quadra.",
+            conferencia.sem_valor
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Instant;
+
+use chrono::NaiveDate;
+use clap::Args;
+
+use mega_da_virada_generator::shared::date;
+use mega_da_virada_generator::{Ball, Enumeracao, GeneratorConfig, Periodo, Restricoes, Result};
+
+use crate::commands::{self, Contexto, generate};
+
+#[derive(Debug, Args)]
+pub struct EnumerateArgs {
+    /// Quantidade de jogos sorteados uniformemente entre os aprovados.
+    #[arg(long, default_value_t = 10)]
+    pub amostra: usize,
+
+    /// Números que entram em todos os jogos (ex.: --fixos 7,13).
+    #[arg(long, value_delimiter = ',')]
+    pub fixos: Vec<Ball>,
+
+    /// Números que nunca entram nos jogos (ex.: --excluidos 1,2,3).
+    #[arg(long, value_delimiter = ',')]
+    pub excluidos: Vec<Ball>,
+
+    /// Semente da amostra, para repeti-la (padrão: aleatória).
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Imprime o resultado em JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Considera apenas concursos a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Considera apenas concursos até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+}
+
+pub fn run(ctx: &Contexto, args: EnumerateArgs) -> Result<()> {
+    let periodo = Periodo::new(args.de, args.ate);
+    let store = ctx.open_store()?;
+    let config = GeneratorConfig {
+        restricoes: Restricoes {
+            fixos: args.fixos,
+            excluidos: args.excluidos,
+        },
+        ..generate::config(&store)?
+    };
+
+    let inicio = Instant::now();
+    let semente = args.seed.unwrap_or_else(rand::random);
+    let enumeracao = store.enumerate(
+        &periodo,
+        &config,
+        &mut commands::gerador(semente),
+        args.amostra,
+    )?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&enumeracao)?;
+        println!("{}", json);
+    } else {
+        print_enumeracao(&enumeracao);
+        println!(
+            "Varredura em {:.1}s com {} threads (semente {})",
+            inicio.elapsed().as_secs_f64(),
+            rayon::current_num_threads(),
+            semente
+        );
+    }
+
+    Ok(())
+}
+
+fn print_enumeracao(enumeracao: &Enumeracao) {
+    println!("Combinações: {}", enumeracao.total);
+    for estagio in &enumeracao.estagios {
+        println!(
+            "  após {:<24} {:>10} ({:.4}%)",
+            estagio.filtro,
+            estagio.sobreviventes,
+            100.0 * estagio.sobreviventes as f64 / enumeracao.total as f64
+        );
+    }
+    println!(
+        "Taxa de aceitação exata: {:.6}%",
+        100.0 * enumeracao.taxa_aceitacao()
+    );
+
+    if !enumeracao.amostra.is_empty() {
+        println!("\nAmostra uniforme dos aprovados:");
+    }
+    for jogo in &enumeracao.amostra {
+        println!("{}", jogo);
+    }
+}
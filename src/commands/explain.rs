@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use clap::Args;
+
+use mega_da_virada_generator::shared::date;
+use mega_da_virada_generator::{Bet, Explicacao, Periodo, Result};
+
+use crate::commands::Contexto;
+use crate::commands::generate::{self, QTD_TOLERAVEL};
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    /// Números do jogo, separados por espaço, vírgula, ponto e vírgula ou hífen.
+    #[arg(required = true, num_args = 1..)]
+    pub numeros: Vec<String>,
+
+    /// Lista os concursos com pelo menos esta quantidade de números em comum.
+    #[arg(short = 'k', long, default_value_t = QTD_TOLERAVEL)]
+    pub minimo: u32,
+
+    /// Imprime o relatório em JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Considera apenas concursos a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Considera apenas concursos até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+}
+
+pub fn run(ctx: &Contexto, args: ExplainArgs) -> Result<()> {
+    let aposta: Bet = args.numeros.join(" ").parse()?;
+    let periodo = Periodo::new(args.de, args.ate);
+
+    let store = ctx.open_store()?;
+    let config = generate::config(&store)?;
+    let explicacao = store.explain(&aposta, &periodo, &config, args.minimo)?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&explicacao)?;
+        println!("{}", json);
+    } else {
+        print_explicacao(&explicacao);
+    }
+
+    Ok(())
+}
+
+fn print_explicacao(explicacao: &Explicacao) {
+    println!("Jogo {}", explicacao.aposta);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    println!("Filtros:");
+    for resultado in &explicacao.filtros {
+        let marca = if resultado.aprovado { "✅" } else { "🚫" };
+        println!("{} {}: {}", marca, resultado.filtro, resultado.motivo);
+    }
+
+    println!(
+        "\nConcursos com {} ou mais números em comum: {}",
+        explicacao.minimo,
+        explicacao.coincidencias.len()
+    );
+    for coincidencia in &explicacao.coincidencias {
+        let comuns: Vec<String> = coincidencia
+            .bolas_em_comum
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        println!(
+            "• Concurso {} ({}): {} acertos — {}",
+            coincidencia.concurso.concurso,
+            date::format_data_br(coincidencia.concurso.data),
+            coincidencia.acertos(),
+            comuns.join(", ")
+        );
+    }
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if explicacao.jogavel {
+        println!("Veredito: jogo aceito pelos filtros.");
+    } else {
+        println!("Veredito: 🚫 jogo bloqueado.");
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{Args, ValueEnum};
+
+use mega_da_virada_generator::export::{self, Formato};
+use mega_da_virada_generator::shared::date;
+use mega_da_virada_generator::{Periodo, Result, SelecaoLotes};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Formato da saída.
+    #[arg(long, value_enum, default_value_t = FormatoExportacao::Markdown)]
+    pub formato: FormatoExportacao,
+
+    /// Lotes a exportar pelo id (padrão: o último lote gravado).
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["de", "ate"])]
+    pub lote: Vec<i64>,
+
+    /// Exporta os lotes criados a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Exporta os lotes criados até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+
+    /// Arquivo de saída (padrão: saída padrão).
+    #[arg(short, long)]
+    pub saida: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatoExportacao {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl From<FormatoExportacao> for Formato {
+    fn from(formato: FormatoExportacao) -> Self {
+        match formato {
+            FormatoExportacao::Csv => Formato::Csv,
+            FormatoExportacao::Json => Formato::Json,
+            FormatoExportacao::Markdown => Formato::Markdown,
+        }
+    }
+}
+
+pub fn run(ctx: &Contexto, args: ExportArgs) -> Result<()> {
+    let selecao = if !args.lote.is_empty() {
+        SelecaoLotes::Ids(args.lote)
+    } else if args.de.is_some() || args.ate.is_some() {
+        SelecaoLotes::Periodo(Periodo::new(args.de, args.ate))
+    } else {
+        SelecaoLotes::Ultimo
+    };
+
+    let store = ctx.open_store()?;
+    let lotes = store.lotes(&selecao)?;
+    if lotes.is_empty() {
+        eprintln!("Nenhum lote encontrado.");
+    }
+
+    let formato = args.formato.into();
+    match &args.saida {
+        Some(caminho) => {
+            export::exportar(&lotes, formato, BufWriter::new(File::create(caminho)?))?;
+            eprintln!(
+                "{} lotes exportados para {}",
+                lotes.len(),
+                caminho.display()
+            );
+        }
+        None => export::exportar(&lotes, formato, io::stdout().lock())?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use clap::Subcommand;
+
+use mega_da_virada_generator::{Flag, Result};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Subcommand)]
+pub enum FlagsCommand {
+    /// Lista as feature flags com o valor efetivo e a origem.
+    List,
+    /// Liga uma feature flag.
+    Enable { flag: Flag },
+    /// Desliga uma feature flag.
+    Disable { flag: Flag },
+}
+
+pub fn run(ctx: &Contexto, command: FlagsCommand) -> Result<()> {
+    let store = ctx.open_store()?;
+
+    match command {
+        FlagsCommand::List => {
+            for estado in store.flags()? {
+                let valor = if estado.enabled {
+                    "ligada"
+                } else {
+                    "desligada"
+                };
+                let origem = if estado.from_database {
+                    "banco"
+                } else {
+                    "padrão"
+                };
+                match estado.flag {
+                    Some(flag) => println!(
+                        "{:<20} {:<10} ({}) — {}",
+                        estado.key,
+                        valor,
+                        origem,
+                        flag.descricao()
+                    ),
+                    None => println!(
+                        "{:<20} {:<10} ({}) — desconhecida",
+                        estado.key, valor, origem
+                    ),
+                }
+            }
+        }
+        FlagsCommand::Enable { flag } => {
+            store.set_flag(flag, true)?;
+            println!("✅ {} ligada.", flag);
+        }
+        FlagsCommand::Disable { flag } => {
+            store.set_flag(flag, false)?;
+            println!("✅ {} desligada.", flag);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+use clap::{Args, ValueEnum};
+
+use mega_da_virada_generator::engine::mega_sena_service::MAX_TENTATIVAS_PADRAO;
+use mega_da_virada_generator::engine::virada::Contagem;
+use mega_da_virada_generator::shared::date;
+use mega_da_virada_generator::{
+    Ball, Bet, Evento, Flag, GeneratorConfig, Ingestao, Objetivo, Orcamento, Otimizacao, Otimizado,
+    Periodo, Rejeicao, Restricoes, Result, Store, Strategy,
+};
+
+use crate::commands::{self, Contexto};
+
+pub const QTD_TOLERAVEL: u32 = 4;
+const QTD_JOGOS_DESEJADOS: usize = 10;
+const AMOSTRAS_ESTIMATIVA: u64 = 10_000;
+const ITERACOES_OTIMIZACAO: u64 = 20_000;
+const LOTES_ALEATORIOS: usize = 10;
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// Considera apenas concursos a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Considera apenas concursos até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+
+    /// Como sortear os números de cada jogo.
+    #[arg(long, value_enum, default_value_t = Estrategia::Uniforme)]
+    pub strategy: Estrategia,
+
+    /// Quantidade de concursos mais recentes usada para calcular os pesos.
+    #[arg(long)]
+    pub janela: Option<usize>,
+
+    /// Inverte os pesos: favorece as bolas frias (frequencia) ou as que
+    /// saíram há pouco (atraso).
+    #[arg(long)]
+    pub inverso: bool,
+
+    /// Números que entram em todos os jogos (ex.: --fixos 7,13).
+    #[arg(long, value_delimiter = ',')]
+    pub fixos: Vec<Ball>,
+
+    /// Números que nunca entram nos jogos (ex.: --excluidos 1,2,3).
+    #[arg(long, value_delimiter = ',')]
+    pub excluidos: Vec<Ball>,
+
+    /// Prefere jogos pouco populares: compara N candidatos aprovados por jogo
+    /// e fica com o de menor popularidade (padrão 20), mostrando o escore.
+    #[arg(long, num_args = 0..=1, default_missing_value = "20", value_name = "N")]
+    pub anti_popular: Option<usize>,
+
+    /// Desiste depois de sortear esta quantidade de candidatos (0 = sem limite).
+    #[arg(long, default_value_t = MAX_TENTATIVAS_PADRAO)]
+    pub max_tentativas: u64,
+
+    /// Desiste depois desta quantidade de segundos.
+    #[arg(long, value_name = "SEGUNDOS")]
+    pub tempo_limite: Option<u64>,
+
+    /// Combinações sorteadas para estimar a viabilidade antes de gerar (0 = não estima).
+    #[arg(long, default_value_t = AMOSTRAS_ESTIMATIVA)]
+    pub amostras: u64,
+
+    /// Semente do sorteio, para repetir um lote (padrão: aleatória, impressa).
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Melhora o lote como um todo por têmpera simulada segundo este objetivo.
+    #[arg(long, value_enum, value_name = "OBJETIVO")]
+    pub otimizar: Option<ObjetivoOtimizacao>,
+
+    /// Trocas avaliadas pela otimização.
+    #[arg(long, default_value_t = ITERACOES_OTIMIZACAO)]
+    pub iteracoes: u64,
+
+    /// Encerra a otimização depois desta quantidade de segundos.
+    #[arg(long, value_name = "SEGUNDOS")]
+    pub tempo_otimizacao: Option<u64>,
+
+    /// Lotes aleatórios gerados para comparar com o lote otimizado.
+    #[arg(long, default_value_t = LOTES_ALEATORIOS)]
+    pub lotes_aleatorios: usize,
+
+    /// Marca o lote para a próxima Mega da Virada e mostra quanto falta.
+    #[arg(long)]
+    pub virada: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Estrategia {
+    /// Todas as bolas com a mesma chance.
+    Uniforme,
+    /// Pesos pela frequência na janela (bolas "quentes").
+    Frequencia,
+    /// Pesos pelo atraso na janela (bolas "atrasadas").
+    Atraso,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ObjetivoOtimizacao {
+    /// Máximo de pares distintos (jogos pouco sobrepostos).
+    Pares,
+    /// Máximo de trios distintos.
+    Trios,
+    /// Todas as bolas aparecendo quase a mesma quantidade de vezes.
+    Equilibrio,
+    /// Menor popularidade média.
+    AntiPopular,
+}
+
+impl From<ObjetivoOtimizacao> for Objetivo {
+    fn from(objetivo: ObjetivoOtimizacao) -> Self {
+        match objetivo {
+            ObjetivoOtimizacao::Pares => Objetivo::CoberturaPares,
+            ObjetivoOtimizacao::Trios => Objetivo::CoberturaTrios,
+            ObjetivoOtimizacao::Equilibrio => Objetivo::Equilibrio,
+            ObjetivoOtimizacao::AntiPopular => Objetivo::AntiPopular,
+        }
+    }
+}
+
+impl GenerateArgs {
+    fn strategy(&self) -> Strategy {
+        let (janela, inverso) = (self.janela, self.inverso);
+        match self.strategy {
+            Estrategia::Uniforme => Strategy::Uniforme,
+            Estrategia::Frequencia => Strategy::Frequencia { janela, inverso },
+            Estrategia::Atraso => Strategy::Atraso { janela, inverso },
+        }
+    }
+
+    fn otimizacao(&self) -> Option<Otimizacao> {
+        self.otimizar.map(|objetivo| Otimizacao {
+            objetivo: objetivo.into(),
+            iteracoes: self.iteracoes,
+            tempo_limite: self.tempo_otimizacao.map(Duration::from_secs),
+            lotes_aleatorios: self.lotes_aleatorios,
+        })
+    }
+}
+
+pub fn run(ctx: &Contexto, args: GenerateArgs) -> Result<()> {
+    let periodo = Periodo::new(args.de, args.ate);
+    let csv_mega_sena_path = "mega_sena.csv";
+
+    let mut store = ctx.open_store()?;
+    println!("Conectado ao SQLite em {}", ctx.db.display());
+
+    match store.bootstrap_from_csv(csv_mega_sena_path)? {
+        Ingestao::JaPopulado => {}
+        Ingestao::SemArquivo => println!(
+            "Arquivo CSV '{}' não encontrado — pulando ingestão.",
+            csv_mega_sena_path
+        ),
+        Ingestao::Importados(n) => println!(
+            "Ingestão do CSV '{}' concluída. {} linhas inseridas.",
+            csv_mega_sena_path, n
+        ),
+    }
+
+    let stats = store.stats(&periodo)?;
+    println!("\n\n\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("Soma minima {}", stats.soma_minima.unwrap_or(0));
+    println!("Soma maxima {}", stats.soma_maxima.unwrap_or(0));
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let print_nao_jogavel = store.flag(Flag::PrintNaoJogavel)?;
+    let config = GeneratorConfig {
+        strategy: args.strategy(),
+        restricoes: Restricoes {
+            fixos: args.fixos.clone(),
+            excluidos: args.excluidos.clone(),
+        },
+        anti_popular: args.anti_popular,
+        orcamento: Orcamento {
+            max_tentativas: (args.max_tentativas > 0).then_some(args.max_tentativas),
+            tempo_limite: args.tempo_limite.map(Duration::from_secs),
+        },
+        otimizacao: args.otimizacao(),
+        ..config(&store)?
+    };
+
+    let semente = args.seed.unwrap_or_else(rand::random);
+    println!("Semente {}", semente);
+    let mut rng = commands::gerador(semente);
+
+    if args.amostras > 0 {
+        let estimativa = store.estimate(&periodo, &config, &mut rng, args.amostras)?;
+        println!(
+            "Viabilidade: {} de {} amostras aprovadas ({:.4}%), ~{} de {} combinações",
+            estimativa.aprovadas,
+            estimativa.amostras,
+            100.0 * estimativa.fracao(),
+            estimativa.combinacoes_aprovadas(),
+            estimativa.espaco
+        );
+        for (filtro, rejeicoes) in &estimativa.rejeicoes {
+            println!("  • {}: {} rejeições", filtro, rejeicoes);
+        }
+        if estimativa.inviavel(&config) {
+            println!(
+                "⚠️  Pedido provavelmente inviável: a taxa estimada não chega a {} jogos \
+                 dentro do orçamento.",
+                config.quantidade
+            );
+        }
+    }
+
+    let on_event = |evento: Evento<'_>| match evento {
+        Evento::Rejeitado(jogo, motivo) if print_nao_jogavel => print_jogo_bloqueado(jogo, motivo),
+        Evento::Progresso(progresso) => println!("⏳ {}", progresso),
+        Evento::Rejeitado(..) => {}
+    };
+    let jogos_gerados = if config.otimizacao.is_some() {
+        let otimizado = store.optimize(&periodo, &config, &mut rng, on_event)?;
+        print_otimizacao(&otimizado);
+        otimizado.jogos
+    } else {
+        store.generate(&periodo, &config, &mut rng, on_event)?
+    };
+
+    let virada = args
+        .virada
+        .then(|| Contagem::a_partir_de(Local::now().date_naive()));
+    let lote_id = store.save_batch(&jogos_gerados, &config, virada.as_ref().map(|c| c.ano))?;
+    println!("Lote {} salvo com {} jogos:", lote_id, jogos_gerados.len());
+    if let Some(contagem) = virada {
+        println!("🎆 Marcado para a {}", contagem);
+    }
+    if config.anti_popular.is_some() {
+        let modelo = store.popularidade(&periodo)?;
+        for jogo in jogos_gerados {
+            println!("{}  popularidade {}", jogo, modelo.pontuar(&jogo));
+        }
+    } else {
+        for jogo in jogos_gerados {
+            println!("{}", jogo);
+        }
+    }
+
+    Ok(())
+}
+
+/// Configuração de geração, com os filtros experimentais ligados pelas flags.
+pub fn config(store: &Store) -> Result<GeneratorConfig> {
+    Ok(GeneratorConfig {
+        quantidade: QTD_JOGOS_DESEJADOS,
+        qtd_toleravel: QTD_TOLERAVEL,
+        ..store.generator_config()?
+    })
+}
+
+fn print_otimizacao(otimizado: &Otimizado) {
+    let objetivo = otimizado.objetivo;
+    println!("Otimização por {}:", objetivo);
+    println!(
+        "  • lote inicial: {}",
+        objetivo.descrever(otimizado.inicial)
+    );
+    if let Some(aleatorio) = otimizado.aleatorio {
+        println!(
+            "  • média de lotes aleatórios: {}",
+            objetivo.descrever(aleatorio)
+        );
+    }
+    println!(
+        "  • lote otimizado: {} ({} trocas aceitas em {} iterações)",
+        objetivo.descrever(otimizado.valor),
+        otimizado.aceitas,
+        otimizado.iteracoes
+    );
+}
+
+fn print_jogo_bloqueado(jogo: &Bet, motivo: &Rejeicao) {
+    let Rejeicao::SobreposicaoHistorico { concurso, .. } = motivo else {
+        return;
+    };
+
+    println!(
+        "\n\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
+     🚫 JOGO BLOQUEADO\n\
+     ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
+     O jogo {} NÃO deve ser jogado.\n\
+     ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
+        jogo
+    );
+
+    println!("Motivo:");
+    println!("• Pelo menos {QTD_TOLERAVEL} números coincidem com um concurso anterior.");
+    println!("• Concurso Nº: {}", concurso.concurso);
+    println!("• Bolas do concurso: {}", Bet::from(concurso));
+    println!("• Data: {}", date::format_data_br(concurso.data));
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+}
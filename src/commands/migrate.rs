@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use rusqlite::Connection;
+
+use mega_da_virada_generator::Result;
+use mega_da_virada_generator::database::migrations::{self, Drift, MigrationState};
+
+use mega_da_virada_generator::database::snapshot;
+
+use crate::commands::{Contexto, avisar_divergencias, confirmar};
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Lista as migrations aplicadas e pendentes com a situação do checksum.
+    Status,
+    /// Aplica as migrations pendentes.
+    Up {
+        /// Para depois desta migration (nome do arquivo, inclusive).
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Ensaia numa cópia em memória e mostra as mudanças de schema,
+        /// sem alterar o banco.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Confere os checksums SHA3 e mostra o relatório de divergências.
+    Verify {
+        /// Diretório com os arquivos .sql para comparar também com o disco.
+        #[arg(long, default_value = "migrations")]
+        dir: PathBuf,
+    },
+    /// Desfaz as últimas migrations aplicadas usando seus scripts `.down.sql`.
+    Rollback {
+        /// Quantidade de migrations a desfazer.
+        #[arg(default_value_t = 1)]
+        n: usize,
+    },
+    /// Aceita as alterações: regrava os checksums divergentes com os atuais.
+    Repair {
+        /// Não pede confirmação.
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+pub fn run(ctx: &Contexto, command: MigrateCommand) -> Result<()> {
+    let conn = Connection::open(&ctx.db)?;
+
+    match command {
+        MigrateCommand::Status => print_status(&conn),
+        MigrateCommand::Up { to, dry_run: true } => {
+            let ensaio = migrations::dry_run(&conn, to.as_deref(), ctx.store_options.drift)?;
+            if ensaio.executadas.is_empty() {
+                println!("Nenhuma migration pendente.");
+                return Ok(());
+            }
+
+            println!("Seriam executadas:");
+            for file_name in &ensaio.executadas {
+                println!("  • {}", file_name);
+            }
+            println!("\nMudanças de schema:");
+            if ensaio.mudancas.is_empty() {
+                println!("  (nenhuma)");
+            }
+            for mudanca in &ensaio.mudancas {
+                println!("  {}", mudanca);
+            }
+            println!("\nEnsaio concluído sem erros; o banco não foi alterado.");
+            Ok(())
+        }
+        MigrateCommand::Up { to, dry_run: false } => {
+            let to = to.as_deref();
+            let drift = ctx.store_options.drift;
+            let executadas = match &ctx.store_options.snapshots {
+                Some(policy) => {
+                    let run = migrations::migrate_up_with_snapshot(&conn, to, drift, policy)?;
+                    if let Some(path) = &run.snapshot {
+                        println!("Snapshot antes das migrations: {}", path.display());
+                    }
+                    run.executadas
+                }
+                None => migrations::migrate_up(&conn, to, drift)?,
+            };
+            avisar_divergencias(&conn, drift)?;
+            for file_name in &executadas {
+                println!("Executada migration: {}", file_name);
+            }
+            if executadas.is_empty() {
+                println!("Nenhuma migration pendente.");
+            } else {
+                println!("{} migration(s) executada(s).", executadas.len());
+            }
+            Ok(())
+        }
+        MigrateCommand::Verify { dir } => {
+            print_status(&conn)?;
+            let report = migrations::drift_report(&conn, Some(&dir))?;
+            print_drift_report(&report, &dir);
+            migrations::verify(&conn)?;
+            println!("Checksums verificados: tudo certo.");
+            Ok(())
+        }
+        MigrateCommand::Rollback { n } => {
+            if let Some(policy) = &ctx.store_options.snapshots {
+                let path = snapshot::take(&conn, policy, "pre-rollback")?;
+                println!("Snapshot antes do rollback: {}", path.display());
+            }
+            let desfeitas = migrations::rollback(&conn, n)?;
+            for file_name in &desfeitas {
+                println!("Desfeita migration: {}", file_name);
+            }
+            println!("{} migration(s) desfeita(s).", desfeitas.len());
+            Ok(())
+        }
+        MigrateCommand::Repair { yes } => {
+            let report: Vec<Drift> = migrations::drift_report(&conn, None)?
+                .into_iter()
+                .filter(Drift::is_recorded)
+                .collect();
+            if report.is_empty() {
+                println!("Nenhum checksum divergente.");
+                return Ok(());
+            }
+
+            print_drift_report(&report, Path::new(""));
+            if !yes && !confirmar("Regravar os checksums acima com os valores atuais?")? {
+                println!("Nada foi alterado.");
+                return Ok(());
+            }
+
+            let corrigidas = migrations::repair(&conn)?;
+            println!("{} checksum(s) regravado(s).", corrigidas.len());
+            Ok(())
+        }
+    }
+}
+
+fn print_status(conn: &Connection) -> Result<()> {
+    for s in migrations::status(conn)? {
+        match s.state {
+            MigrationState::Applied { executed_at } => {
+                println!("✅ {:<45} aplicada em {}", s.file_name, executed_at)
+            }
+            MigrationState::Pending => println!("⏳ {:<45} pendente", s.file_name),
+            MigrationState::ChecksumMismatch { stored, current } => println!(
+                "❌ {:<45} checksum divergente (banco {}, atual {})",
+                s.file_name, stored, current
+            ),
+            MigrationState::Unknown { executed_at } => println!(
+                "❓ {:<45} aplicada em {}, mas desconhecida por este binário",
+                s.file_name, executed_at
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn print_drift_report(report: &[Drift], dir: &Path) {
+    if report.is_empty() {
+        return;
+    }
+
+    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("Divergências de checksum SHA3");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for d in report {
+        println!("• {}", d.file_name);
+        println!("  banco:    {}", d.stored);
+        println!("  embutido: {}", d.embedded);
+        match &d.on_disk {
+            Some(on_disk) if *on_disk != d.embedded => println!(
+                "  disco:    {} (arquivo em {} alterado; recompile)",
+                on_disk,
+                dir.display()
+            ),
+            Some(on_disk) => println!("  disco:    {}", on_disk),
+            None => {}
+        }
+    }
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+}
@@ -0,0 +1,81 @@
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use mega_da_virada_generator::database::migrations::{self, Drift};
+use mega_da_virada_generator::{DriftMode, Result, Store, StoreOptions};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rusqlite::Connection;
+
+pub mod backup;
+pub mod check;
+pub mod enumerate;
+pub mod explain;
+pub mod export;
+pub mod flags;
+pub mod generate;
+pub mod migrate;
+pub mod relatorio;
+pub mod serve;
+pub mod tui;
+pub mod virada;
+pub mod volante;
+
+/// Opções globais da linha de comando, compartilhadas pelos subcomandos.
+pub struct Contexto {
+    pub db: PathBuf,
+    pub store_options: StoreOptions,
+}
+
+impl Contexto {
+    /// Abre o banco aplicando as migrations pendentes. Os avisos vão para
+    /// stderr, para não misturar com a saída dos comandos (JSON, CSV...).
+    pub fn open_store(&self) -> Result<Store> {
+        let store = Store::open_with(&self.db, &self.store_options)?;
+        if let Some(path) = store.snapshot_pre_migracao() {
+            eprintln!("Snapshot antes das migrations: {}", path.display());
+        }
+        for file_name in store.migracoes_aplicadas() {
+            eprintln!("Executada migration: {}", file_name);
+        }
+        avisar_divergencias(store.connection(), self.store_options.drift)?;
+        Ok(store)
+    }
+}
+
+/// No modo leniente as migrations seguem apesar dos checksums divergentes;
+/// o aviso sai em stderr.
+pub fn avisar_divergencias(conn: &Connection, drift: DriftMode) -> Result<()> {
+    if drift != DriftMode::Lenient {
+        return Ok(());
+    }
+    for d in migrations::drift_report(conn, None)?
+        .into_iter()
+        .filter(Drift::is_recorded)
+    {
+        eprintln!(
+            "⚠️  Checksum divergente em {} (banco {}, atual {}) — seguindo em modo leniente.",
+            d.file_name, d.stored, d.embedded
+        );
+    }
+    Ok(())
+}
+
+/// Pergunta sim/não no terminal; qualquer resposta diferente de "s" é não.
+pub fn confirmar(pergunta: &str) -> Result<bool> {
+    print!("{} [s/N] ", pergunta);
+    io::stdout().flush()?;
+
+    let mut resposta = String::new();
+    io::stdin().lock().read_line(&mut resposta)?;
+    Ok(matches!(
+        resposta.trim().to_lowercase().as_str(),
+        "s" | "sim" | "y" | "yes"
+    ))
+}
+
+/// Gerador semeado: com a mesma semente, geração e varredura repetem o
+/// resultado com qualquer `--threads`.
+pub fn gerador(semente: u64) -> StdRng {
+    StdRng::seed_from_u64(semente)
+}
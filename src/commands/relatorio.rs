@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate};
+use clap::Args;
+
+use mega_da_virada_generator::engine::virada;
+use mega_da_virada_generator::export::relatorio::{self, Cabecalho};
+use mega_da_virada_generator::shared::date;
+use mega_da_virada_generator::{Periodo, Result, SelecaoLotes};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Args)]
+pub struct RelatorioArgs {
+    /// Considera os concursos a partir desta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub de: Option<NaiveDate>,
+
+    /// Considera os concursos até esta data (dd/mm/yyyy ou yyyy-mm-dd).
+    #[arg(long, value_parser = date::parse_data)]
+    pub ate: Option<NaiveDate>,
+
+    /// Usa só os concursos da Mega da Virada.
+    #[arg(long)]
+    pub virada: bool,
+
+    /// Lote apresentado no relatório (padrão: o último lote gravado).
+    #[arg(long)]
+    pub lote: Option<i64>,
+
+    /// Título no topo da página.
+    #[arg(long, default_value = "Estatísticas da Mega-Sena")]
+    pub titulo: String,
+
+    /// Arquivo HTML gerado.
+    #[arg(short, long, default_value = "relatorio_mega_sena.html")]
+    pub saida: PathBuf,
+}
+
+pub fn run(ctx: &Contexto, args: RelatorioArgs) -> Result<()> {
+    let store = ctx.open_store()?;
+    let periodo = Periodo::new(args.de, args.ate);
+    let mut historico = store.historico(&periodo)?;
+    if args.virada {
+        historico.retain(virada::e_virada);
+    }
+    let selecao = match args.lote {
+        Some(id) => SelecaoLotes::Ids(vec![id]),
+        None => SelecaoLotes::Ultimo,
+    };
+    let lote = store.lotes(&selecao)?.into_iter().next();
+
+    let cabecalho = Cabecalho {
+        titulo: args.titulo,
+        periodo,
+        so_viradas: args.virada,
+        gerado_em: Local::now().date_naive(),
+    };
+    fs::write(
+        &args.saida,
+        relatorio::html(&historico, lote.as_ref(), &cabecalho),
+    )?;
+    println!(
+        "Relatório de {} concursos gravado em {}",
+        historico.len(),
+        args.saida.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use std::io;
+
+use clap::Args;
+use tiny_http::{Header, Method, Response, Server};
+
+use mega_da_virada_generator::api::{self, Resposta};
+use mega_da_virada_generator::{Error, Result};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Endereço em que o servidor escuta.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub endereco: String,
+
+    /// Libera chamadas de navegador vindas desta origem (CORS), ex.:
+    /// --cors http://localhost:3000.
+    #[arg(long)]
+    pub cors: Option<String>,
+}
+
+pub fn run(ctx: &Contexto, args: ServeArgs) -> Result<()> {
+    let mut store = ctx.open_store()?;
+    let cors = args
+        .cors
+        .as_deref()
+        .map(|origem| cabecalho("Access-Control-Allow-Origin", origem))
+        .transpose()?;
+    let json = cabecalho("Content-Type", "application/json; charset=utf-8")?;
+
+    let server = Server::http(&args.endereco).map_err(|e| Error::Io(io::Error::other(e)))?;
+    println!("API em http://{} (Ctrl+C encerra)", args.endereco);
+
+    // O banco é um só; as requisições são atendidas uma de cada vez.
+    for mut request in server.incoming_requests() {
+        let metodo = request.method().clone();
+        let url = request.url().to_string();
+
+        if metodo == Method::Options && cors.is_some() {
+            let mut preflight = Response::empty(204)
+                .with_header(cabecalho("Access-Control-Allow-Methods", "GET, POST")?)
+                .with_header(cabecalho("Access-Control-Allow-Headers", "Content-Type")?);
+            if let Some(cors) = &cors {
+                preflight.add_header(cors.clone());
+            }
+            if let Err(e) = request.respond(preflight) {
+                eprintln!("Falha ao responder {} {}: {}", metodo, url, e);
+            }
+            continue;
+        }
+
+        let mut corpo = Vec::new();
+        let resposta = match request.as_reader().read_to_end(&mut corpo) {
+            Ok(_) => api::atender(&mut store, metodo.as_str(), &url, &corpo),
+            Err(e) => Resposta::from(Error::from(e)),
+        };
+        println!("{} {} → {}", metodo, url, resposta.status);
+
+        let mut response = Response::from_string(resposta.corpo.to_string())
+            .with_status_code(resposta.status)
+            .with_header(json.clone());
+        if let Some(cors) = &cors {
+            response.add_header(cors.clone());
+        }
+        if let Err(e) = request.respond(response) {
+            eprintln!("Falha ao responder {} {}: {}", metodo, url, e);
+        }
+    }
+
+    Ok(())
+}
+
+fn cabecalho(nome: &str, valor: &str) -> Result<Header> {
+    Header::from_bytes(nome.as_bytes(), valor.as_bytes())
+        .map_err(|()| Error::Config(format!("cabeçalho HTTP inválido: {}: {}", nome, valor)))
+}
@@ -0,0 +1,29 @@
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use mega_da_virada_generator::Result;
+use mega_da_virada_generator::tui::{Controle, Painel};
+
+use crate::commands::{self, Contexto};
+
+pub fn run(ctx: &Contexto) -> Result<()> {
+    let store = ctx.open_store()?;
+    let mut painel = Painel::new(store, commands::gerador(rand::random()))?;
+
+    // `init` troca para a tela alternativa e restaura o terminal em caso de panic.
+    let mut terminal = ratatui::try_init()?;
+    let resultado = laco(&mut terminal, &mut painel);
+    ratatui::restore();
+    resultado
+}
+
+fn laco(terminal: &mut ratatui::DefaultTerminal, painel: &mut Painel) -> Result<()> {
+    loop {
+        terminal.draw(|frame| painel.desenhar(frame))?;
+        if let Event::Key(tecla) = event::read()?
+            && tecla.kind == KeyEventKind::Press
+            && painel.tecla(tecla) == Controle::Sai
+        {
+            return Ok(());
+        }
+    }
+}
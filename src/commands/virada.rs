@@ -0,0 +1,115 @@
+use chrono::Local;
+use clap::Args;
+
+use mega_da_virada_generator::shared::{date, dinheiro};
+use mega_da_virada_generator::{Lote, PanoramaVirada, Result, SelecaoLotes};
+
+use crate::commands::Contexto;
+
+/// Bolas listadas como as mais sorteadas nas Viradas.
+const MAIS_SORTEADAS: usize = 10;
+
+#[derive(Debug, Args)]
+pub struct ViradaArgs {
+    /// Imprime o panorama em JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(ctx: &Contexto, args: ViradaArgs) -> Result<()> {
+    let store = ctx.open_store()?;
+    let panorama = store.virada(Local::now().date_naive())?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&panorama)?;
+        println!("{}", json);
+    } else {
+        let lotes = store.lotes(&SelecaoLotes::Virada(panorama.proxima.ano))?;
+        print_panorama(&panorama, &lotes);
+    }
+
+    Ok(())
+}
+
+fn print_panorama(panorama: &PanoramaVirada, lotes: &[Lote]) {
+    println!("⏳ {}", panorama.proxima);
+    if let Some(reserva) = panorama.reserva {
+        println!(
+            "Reserva acumulada para a Virada: {} (concurso {})",
+            dinheiro::format_reais(reserva.centavos),
+            reserva.concurso
+        );
+    }
+    if let Some(acumulado) = panorama.acumulado_sena {
+        println!(
+            "Prêmio principal acumulado: {} (concurso {})",
+            dinheiro::format_reais(acumulado.centavos),
+            acumulado.concurso
+        );
+    }
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if panorama.viradas.is_empty() {
+        println!("Nenhuma Mega da Virada no histórico importado.");
+    } else {
+        println!("Viradas no histórico ({}):", panorama.viradas.len());
+        for virada in panorama.viradas.iter().rev() {
+            let concurso = &virada.concurso;
+            let rateio = virada
+                .rateio_sena
+                .map(|valor| format!(" — {} por ganhador", dinheiro::format_reais(valor)))
+                .unwrap_or_default();
+            println!(
+                "• Concurso {} ({}): {}{}",
+                concurso.concurso,
+                date::format_data_br(concurso.data),
+                concurso
+                    .bolas()
+                    .iter()
+                    .map(|b| b.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                rateio
+            );
+        }
+
+        let stats = &panorama.estatisticas;
+        let mut bolas: Vec<usize> = (0..stats.frequencia.len()).collect();
+        bolas.sort_by_key(|&i| std::cmp::Reverse(stats.frequencia[i]));
+        let mais_sorteadas: Vec<String> = bolas
+            .iter()
+            .take(MAIS_SORTEADAS)
+            .map(|&i| format!("{:02} ({}x)", i + 1, stats.frequencia[i]))
+            .collect();
+        let nunca = stats.frequencia.iter().filter(|&&f| f == 0).count();
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("Estatísticas só das Viradas:");
+        println!("Mais sorteadas: {}", mais_sorteadas.join(", "));
+        println!("Bolas que nunca saíram numa Virada: {}", nunca);
+        println!(
+            "Soma entre {} e {}",
+            stats.soma_minima.unwrap_or(0),
+            stats.soma_maxima.unwrap_or(0)
+        );
+        let pares: Vec<String> = stats
+            .pares
+            .iter()
+            .enumerate()
+            .map(|(i, n)| format!("{}: {}", i, n))
+            .collect();
+        println!("Viradas por bolas pares — {}", pares.join(" · "));
+    }
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if lotes.is_empty() {
+        println!(
+            "Nenhum lote marcado para a Virada {}; gere com `generate --virada`.",
+            panorama.proxima.ano
+        );
+    } else {
+        println!("Lotes para a Virada {}:", panorama.proxima.ano);
+        for lote in lotes {
+            println!("• Lote {} com {} jogos", lote.id, lote.apostas.len());
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+use mega_da_virada_generator::export::volante::{self, Cabecalho};
+use mega_da_virada_generator::{Bet, Result, SelecaoLotes};
+
+use crate::commands::Contexto;
+
+#[derive(Debug, Args)]
+pub struct VolanteArgs {
+    /// Lote a imprimir (padrão: o último lote gravado).
+    #[arg(long)]
+    pub lote: Option<i64>,
+
+    /// Nome do bolão, impresso no topo de cada volante.
+    #[arg(long)]
+    pub bolao: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = FormatoVolante::Pdf)]
+    pub formato: FormatoVolante,
+
+    /// Arquivo PDF ou diretório dos SVGs (padrão: volantes_lote_<id>).
+    #[arg(short, long)]
+    pub saida: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatoVolante {
+    /// Um PDF A4 com um volante por página.
+    Pdf,
+    /// Um SVG por jogo, num diretório.
+    Svg,
+}
+
+pub fn run(ctx: &Contexto, args: VolanteArgs) -> Result<()> {
+    let selecao = match args.lote {
+        Some(id) => SelecaoLotes::Ids(vec![id]),
+        None => SelecaoLotes::Ultimo,
+    };
+    let store = ctx.open_store()?;
+    let Some(lote) = store.lotes(&selecao)?.into_iter().next() else {
+        println!("Nenhum lote gravado.");
+        return Ok(());
+    };
+
+    let cabecalho = Cabecalho {
+        bolao: args.bolao,
+        lote: Some(lote.id),
+    };
+    let jogos: Vec<Bet> = lote.apostas.into_iter().map(|a| a.numeros).collect();
+    let padrao = format!("volantes_lote_{}", lote.id);
+
+    match args.formato {
+        FormatoVolante::Pdf => {
+            let caminho = args
+                .saida
+                .unwrap_or_else(|| PathBuf::from(format!("{}.pdf", padrao)));
+            fs::write(&caminho, volante::pdf(&jogos, &cabecalho))?;
+            println!("{} volantes gravados em {}", jogos.len(), caminho.display());
+        }
+        FormatoVolante::Svg => {
+            let dir = args.saida.unwrap_or_else(|| PathBuf::from(padrao));
+            fs::create_dir_all(&dir)?;
+            for (i, jogo) in jogos.iter().enumerate() {
+                let svg = volante::svg(jogo, i + 1, jogos.len(), &cabecalho);
+                fs::write(dir.join(format!("jogo_{:02}.svg", i + 1)), svg)?;
+            }
+            println!("{} volantes gravados em {}", jogos.len(), dir.display());
+        }
+    }
+
+    Ok(())
+}
@@ -1,57 +1,389 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::str::FromStr;
 
+use chrono::NaiveDate;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Type, ValueRef};
 use rusqlite::{Result, Row};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MegaSena {
-    pub id: i64,
-    pub concurso: i64,
-    pub data: String,
-    pub bola_1: Option<i64>,
-    pub bola_2: Option<i64>,
-    pub bola_3: Option<i64>,
-    pub bola_4: Option<i64>,
-    pub bola_5: Option<i64>,
-    pub bola_6: Option<i64>,
-    pub inserted_at: String,
-    pub set: HashSet<i64>,
-    pub generated_by_rust: bool,
-}
-
-impl<'a> TryFrom<&Row<'a>> for MegaSena {
+use crate::shared::date;
+
+/// Menor número do volante.
+pub const MENOR_BOLA: u8 = 1;
+/// Maior número do volante.
+pub const MAIOR_BOLA: u8 = 60;
+/// Quantidade de bolas sorteadas em cada concurso.
+pub const BOLAS_POR_SORTEIO: usize = 6;
+/// Maior aposta permitida pela Caixa (20 números marcados).
+pub const MAX_BOLAS_APOSTA: usize = 20;
+/// Jogos distintos de seis números: C(60, 6).
+pub const TOTAL_COMBINACOES: u64 = 50_063_860;
+
+/// Coeficiente binomial C(n, k).
+pub fn combinacoes(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Motivo pelo qual um jogo foi rejeitado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidGame {
+    BolaForaDoIntervalo(i64),
+    BolaRepetida(u8),
+    QuantidadeInvalida { esperado: String, encontrado: usize },
+    NumeroInvalido(String),
+}
+
+impl fmt::Display for InvalidGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BolaForaDoIntervalo(n) => write!(
+                f,
+                "a bola {} está fora do intervalo {}..={}",
+                n, MENOR_BOLA, MAIOR_BOLA
+            ),
+            Self::BolaRepetida(n) => write!(f, "a bola {} aparece mais de uma vez", n),
+            Self::QuantidadeInvalida {
+                esperado,
+                encontrado,
+            } => write!(
+                f,
+                "quantidade de bolas inválida: esperado {}, encontrado {}",
+                esperado, encontrado
+            ),
+            Self::NumeroInvalido(s) => write!(f, "'{}' não é um número válido", s),
+        }
+    }
+}
+
+impl std::error::Error for InvalidGame {}
+
+/// Uma bola do volante, garantidamente entre 1 e 60.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct Ball(u8);
+
+impl Ball {
+    pub fn new(n: u8) -> Result<Self, InvalidGame> {
+        if (MENOR_BOLA..=MAIOR_BOLA).contains(&n) {
+            Ok(Self(n))
+        } else {
+            Err(InvalidGame::BolaForaDoIntervalo(n as i64))
+        }
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Todas as 60 bolas do volante, em ordem.
+    pub fn all() -> impl Iterator<Item = Ball> {
+        (MENOR_BOLA..=MAIOR_BOLA).map(Ball)
+    }
+
+    fn bit(self) -> u64 {
+        1u64 << self.0
+    }
+}
+
+impl TryFrom<u8> for Ball {
+    type Error = InvalidGame;
+
+    fn try_from(n: u8) -> Result<Self, InvalidGame> {
+        Self::new(n)
+    }
+}
+
+impl TryFrom<i64> for Ball {
+    type Error = InvalidGame;
+
+    fn try_from(n: i64) -> Result<Self, InvalidGame> {
+        u8::try_from(n)
+            .map_err(|_| InvalidGame::BolaForaDoIntervalo(n))
+            .and_then(Self::new)
+    }
+}
+
+impl From<Ball> for u8 {
+    fn from(ball: Ball) -> u8 {
+        ball.0
+    }
+}
+
+impl FromStr for Ball {
+    type Err = InvalidGame;
+
+    fn from_str(s: &str) -> Result<Self, InvalidGame> {
+        let n: i64 = s
+            .trim()
+            .parse()
+            .map_err(|_| InvalidGame::NumeroInvalido(s.trim().to_string()))?;
+        Self::try_from(n)
+    }
+}
+
+impl fmt::Display for Ball {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+impl ToSql for Ball {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0 as i64))
+    }
+}
+
+impl FromSql for Ball {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let n = i64::column_result(value)?;
+        Ball::try_from(n).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+/// Ordena e valida um conjunto de bolas, rejeitando repetições.
+fn sorted_unique(mut bolas: Vec<Ball>) -> Result<Vec<Ball>, InvalidGame> {
+    bolas.sort_unstable();
+    if let Some(w) = bolas.windows(2).find(|w| w[0] == w[1]) {
+        return Err(InvalidGame::BolaRepetida(w[0].value()));
+    }
+    Ok(bolas)
+}
+
+fn mask_of(bolas: &[Ball]) -> u64 {
+    bolas.iter().fold(0, |acc, b| acc | b.bit())
+}
+
+fn write_bolas(f: &mut fmt::Formatter<'_>, bolas: &[Ball]) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, b) in bolas.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", b)?;
+    }
+    write!(f, "]")
+}
+
+/// Resultado oficial de um concurso: seis bolas distintas, ordenadas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawDraw")]
+pub struct Draw {
+    pub concurso: u32,
+    pub data: NaiveDate,
+    bolas: [Ball; BOLAS_POR_SORTEIO],
+}
+
+#[derive(Deserialize)]
+struct RawDraw {
+    concurso: u32,
+    data: NaiveDate,
+    bolas: Vec<Ball>,
+}
+
+impl TryFrom<RawDraw> for Draw {
+    type Error = InvalidGame;
+
+    fn try_from(raw: RawDraw) -> Result<Self, InvalidGame> {
+        Draw::new(raw.concurso, raw.data, raw.bolas)
+    }
+}
+
+impl Draw {
+    pub fn new(concurso: u32, data: NaiveDate, bolas: Vec<Ball>) -> Result<Self, InvalidGame> {
+        let bolas = sorted_unique(bolas)?;
+        let encontrado = bolas.len();
+        let bolas: [Ball; BOLAS_POR_SORTEIO] =
+            bolas
+                .try_into()
+                .map_err(|_| InvalidGame::QuantidadeInvalida {
+                    esperado: BOLAS_POR_SORTEIO.to_string(),
+                    encontrado,
+                })?;
+        Ok(Self {
+            concurso,
+            data,
+            bolas,
+        })
+    }
+
+    pub fn bolas(&self) -> &[Ball; BOLAS_POR_SORTEIO] {
+        &self.bolas
+    }
+
+    /// Conjunto das bolas como bitmask (bit `n` ligado para a bola `n`).
+    pub fn mask(&self) -> u64 {
+        mask_of(&self.bolas)
+    }
+
+    pub fn soma(&self) -> u32 {
+        self.bolas.iter().map(|b| b.value() as u32).sum()
+    }
+}
+
+/// Lê a data da linha, preferindo a coluna tipada `data_typesafe` e caindo
+/// para o texto `dd/mm/yyyy` de `data` quando ela ainda não foi preenchida.
+fn data_from_row(row: &Row) -> Result<NaiveDate> {
+    if let Some(data) = row.get::<_, Option<NaiveDate>>("data_typesafe")? {
+        return Ok(data);
+    }
+
+    let texto: String = row.get("data")?;
+    date::parse_data(&texto).map_err(|e| {
+        let idx = row.as_ref().column_index("data").unwrap_or(0);
+        rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e))
+    })
+}
+
+impl<'a> TryFrom<&Row<'a>> for Draw {
     type Error = rusqlite::Error;
 
     fn try_from(row: &Row) -> Result<Self> {
-        Ok(Self {
-            id: row.get("id")?,
-            concurso: row.get("concurso")?,
-            data: row.get("data")?,
-            bola_1: row.get("bola_1")?,
-            bola_2: row.get("bola_2")?,
-            bola_3: row.get("bola_3")?,
-            bola_4: row.get("bola_4")?,
-            bola_5: row.get("bola_5")?,
-            bola_6: row.get("bola_6")?,
-            inserted_at: row.get("inserted_at")?,
-            generated_by_rust: row.get("generated_by_rust")?,
-            set: HashSet::new(),
+        let bolas = vec![
+            row.get("bola_1")?,
+            row.get("bola_2")?,
+            row.get("bola_3")?,
+            row.get("bola_4")?,
+            row.get("bola_5")?,
+            row.get("bola_6")?,
+        ];
+        let concurso: u32 = row.get("concurso")?;
+        Draw::new(concurso, data_from_row(row)?, bolas).map_err(|e| {
+            let idx = row.as_ref().column_index("bola_1").unwrap_or(0);
+            rusqlite::Error::FromSqlConversionFailure(idx, Type::Integer, Box::new(e))
         })
     }
 }
 
-impl fmt::Display for MegaSena {
+impl fmt::Display for Draw {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "[{:?}, {:?}, {:?}, {:?}, {:?}, {:?}]",
-            self.bola_1.unwrap_or(99),
-            self.bola_2.unwrap_or(99),
-            self.bola_3.unwrap_or(99),
-            self.bola_4.unwrap_or(99),
-            self.bola_5.unwrap_or(99),
-            self.bola_6.unwrap_or(99)
-        )
+            "Concurso {} ({}): ",
+            self.concurso,
+            date::format_data_br(self.data)
+        )?;
+        write_bolas(f, &self.bolas)
+    }
+}
+
+/// Uma aposta: de 6 a 20 bolas distintas, ordenadas.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Ball>", into = "Vec<Ball>")]
+pub struct Bet {
+    bolas: Vec<Ball>,
+}
+
+impl Bet {
+    pub fn new(bolas: Vec<Ball>) -> Result<Self, InvalidGame> {
+        let bolas = sorted_unique(bolas)?;
+        if !(BOLAS_POR_SORTEIO..=MAX_BOLAS_APOSTA).contains(&bolas.len()) {
+            return Err(InvalidGame::QuantidadeInvalida {
+                esperado: format!("{}..={}", BOLAS_POR_SORTEIO, MAX_BOLAS_APOSTA),
+                encontrado: bolas.len(),
+            });
+        }
+        Ok(Self { bolas })
+    }
+
+    /// Monta uma aposta a partir de números soltos, validando cada um.
+    pub fn from_numbers(numeros: &[i64]) -> Result<Self, InvalidGame> {
+        let bolas = numeros
+            .iter()
+            .map(|&n| Ball::try_from(n))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(bolas)
+    }
+
+    pub fn bolas(&self) -> &[Ball] {
+        &self.bolas
+    }
+
+    pub fn len(&self) -> usize {
+        self.bolas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bolas.is_empty()
+    }
+
+    pub fn contains(&self, ball: Ball) -> bool {
+        self.bolas.binary_search(&ball).is_ok()
+    }
+
+    /// Conjunto das bolas como bitmask (bit `n` ligado para a bola `n`).
+    pub fn mask(&self) -> u64 {
+        mask_of(&self.bolas)
+    }
+
+    pub fn soma(&self) -> u32 {
+        self.bolas.iter().map(|b| b.value() as u32).sum()
+    }
+
+    /// Números da aposta com dois dígitos, separados por `sep` (ex.: `04,12,23`).
+    pub fn join(&self, sep: &str) -> String {
+        self.bolas
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    /// Quantidade de bolas do concurso que aparecem nesta aposta.
+    pub fn acertos(&self, draw: &Draw) -> u32 {
+        (self.mask() & draw.mask()).count_ones()
+    }
+
+    /// Bolas do concurso que aparecem nesta aposta.
+    pub fn bolas_em_comum(&self, draw: &Draw) -> Vec<Ball> {
+        draw.bolas()
+            .iter()
+            .copied()
+            .filter(|b| self.contains(*b))
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<Ball>> for Bet {
+    type Error = InvalidGame;
+
+    fn try_from(bolas: Vec<Ball>) -> Result<Self, InvalidGame> {
+        Self::new(bolas)
+    }
+}
+
+impl From<&Draw> for Bet {
+    fn from(draw: &Draw) -> Self {
+        Self {
+            bolas: draw.bolas.to_vec(),
+        }
+    }
+}
+
+impl From<Bet> for Vec<Ball> {
+    fn from(bet: Bet) -> Vec<Ball> {
+        bet.bolas
+    }
+}
+
+/// Aceita números separados por vírgula, espaço, hífen ou ponto e vírgula.
+impl FromStr for Bet {
+    type Err = InvalidGame;
+
+    fn from_str(s: &str) -> Result<Self, InvalidGame> {
+        let bolas = s
+            .split(|c: char| c == ',' || c == ';' || c == '-' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .map(Ball::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(bolas)
+    }
+}
+
+impl fmt::Display for Bet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_bolas(f, &self.bolas)
     }
 }
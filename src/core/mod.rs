@@ -1 +1,2 @@
 pub mod mega_sena;
+pub mod periodo;
@@ -0,0 +1,25 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Intervalo de datas (inclusivo) usado para filtrar consultas ao histórico.
+/// Um limite `None` significa "sem limite" daquele lado.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Periodo {
+    pub de: Option<NaiveDate>,
+    pub ate: Option<NaiveDate>,
+}
+
+impl Periodo {
+    pub fn new(de: Option<NaiveDate>, ate: Option<NaiveDate>) -> Self {
+        Self { de, ate }
+    }
+
+    /// Período sem limites: todo o histórico.
+    pub fn todos() -> Self {
+        Self::default()
+    }
+
+    pub fn contem(&self, data: NaiveDate) -> bool {
+        self.de.is_none_or(|de| data >= de) && self.ate.is_none_or(|ate| data <= ate)
+    }
+}
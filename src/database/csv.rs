@@ -1,21 +1,52 @@
-use anyhow::{Context, Result};
+use std::path::Path;
+
 use rusqlite::{Connection, params};
 
-pub fn ingest_csv_mega_sena_to_sqlite(conn: &mut Connection, csv_path: &str) -> Result<()> {
+use crate::core::mega_sena::{Ball, Draw};
+use crate::error::{Error, Result};
+use crate::shared::{date, dinheiro};
+
+/// Colunas opcionais em reais: o rateio por ganhador de cada faixa, na
+/// ordem sena, quina e quadra, e os valores acumulados do prêmio principal e
+/// da reserva da Mega da Virada. Aceita os nomes do arquivo da Caixa.
+const COLUNAS_EM_REAIS: [&[&str]; 5] = [
+    &["rateio_sena", "rateio 6 acertos"],
+    &["rateio_quina", "rateio 5 acertos"],
+    &["rateio_quadra", "rateio 4 acertos"],
+    &["acumulado_sena", "acumulado 6 acertos"],
+    &["acumulado_virada", "acumulado sorteio especial mega da virada"],
+];
+
+/// Importa os concursos de um CSV da Caixa e devolve quantos eram novos.
+/// Concursos já gravados são mantidos, completando o rateio e os acumulados.
+pub fn ingest_csv_mega_sena_to_sqlite(conn: &mut Connection, csv_path: &Path) -> Result<usize> {
+    let origem = csv_path.display().to_string();
     let mut rdr = csv::ReaderBuilder::new()
         .delimiter(b',')
         .has_headers(true)
         .from_path(csv_path)
-        .with_context(|| format!("Falha ao abrir CSV {}", csv_path))?;
+        .map_err(|e| Error::import(&origem, format!("falha ao abrir CSV: {}", e)))?;
+
+    let cabecalho = rdr
+        .headers()
+        .map_err(|e| Error::import(&origem, e))?
+        .clone();
+    let colunas_em_reais = COLUNAS_EM_REAIS.map(|nomes| {
+        cabecalho
+            .iter()
+            .position(|h| nomes.contains(&h.trim().to_lowercase().as_str()))
+    });
 
     let tx = conn.transaction()?;
     let mut inserted = 0usize;
 
     for result in rdr.records() {
-        let record = result?;
+        let record = result.map_err(|e| Error::import(&origem, e))?;
+        let linha = record.position().map(|p| p.line()).unwrap_or(0);
+        let erro = |motivo: String| Error::import(format!("{}:{}", origem, linha), motivo);
+
         if record.len() < 8 {
-            eprintln!("Linha ignorada (colunas insuficientes): {:?}", record);
-            continue;
+            return Err(erro(format!("colunas insuficientes: esperadas 8, encontradas {}", record.len())));
         }
 
         let strip_quotes = |s: &str| -> String {
@@ -26,27 +57,63 @@ pub fn ingest_csv_mega_sena_to_sqlite(conn: &mut Connection, csv_path: &str) ->
                 .to_string()
         };
 
-        let concurso: i64 = strip_quotes(&record[0]).parse()?;
+        let concurso_texto = strip_quotes(&record[0]);
+        let concurso: u32 = concurso_texto
+            .parse()
+            .map_err(|_| erro(format!("concurso inválido: {}", concurso_texto)))?;
         let data = strip_quotes(&record[1]);
-        let bola_1: i64 = strip_quotes(&record[2]).parse()?;
-        let bola_2: i64 = strip_quotes(&record[3]).parse()?;
-        let bola_3: i64 = strip_quotes(&record[4]).parse()?;
-        let bola_4: i64 = strip_quotes(&record[5]).parse()?;
-        let bola_5: i64 = strip_quotes(&record[6]).parse()?;
-        let bola_6: i64 = strip_quotes(&record[7]).parse()?;
-
-        tx.execute(
+        let data_typesafe = date::parse_data(&data)
+            .map_err(|_| erro(format!("data inválida no concurso {}: {}", concurso, data)))?;
+        let bolas = (2..8)
+            .map(|i| strip_quotes(&record[i]).parse::<Ball>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| erro(format!("concurso {}: {}", concurso, e)))?;
+        Draw::new(concurso, data_typesafe, bolas.clone())
+            .map_err(|e| erro(format!("concurso {}: {}", concurso, e)))?;
+
+        let mut valores = [None; 5];
+        for (valor, coluna) in valores.iter_mut().zip(colunas_em_reais) {
+            let Some(texto) = coluna.and_then(|i| record.get(i)).map(strip_quotes) else {
+                continue;
+            };
+            if texto.is_empty() {
+                continue;
+            }
+            *valor = Some(dinheiro::parse_reais(&texto).ok_or_else(|| {
+                erro(format!("concurso {}: valor em reais inválido: {}", concurso, texto))
+            })?);
+        }
+
+        // Valida como concurso, mas grava as bolas na ordem do CSV.
+        let novo = tx.execute(
             "INSERT INTO t_mega_sena
-                (concurso, data, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6,
+                 rateio_sena_centavos, rateio_quina_centavos, rateio_quadra_centavos,
+                 acumulado_sena_centavos, acumulado_virada_centavos)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT (concurso) DO NOTHING",
             params![
-                concurso, data, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6
+                concurso, data, data_typesafe, bolas[0], bolas[1], bolas[2], bolas[3], bolas[4],
+                bolas[5], valores[0], valores[1], valores[2], valores[3], valores[4]
             ],
         )?;
-        inserted += 1;
+
+        // Concurso já importado: só completa os valores em reais que faltavam.
+        if novo == 0 {
+            tx.execute(
+                "UPDATE t_mega_sena
+                 SET rateio_sena_centavos = COALESCE(?2, rateio_sena_centavos),
+                     rateio_quina_centavos = COALESCE(?3, rateio_quina_centavos),
+                     rateio_quadra_centavos = COALESCE(?4, rateio_quadra_centavos),
+                     acumulado_sena_centavos = COALESCE(?5, acumulado_sena_centavos),
+                     acumulado_virada_centavos = COALESCE(?6, acumulado_virada_centavos)
+                 WHERE concurso = ?1",
+                params![concurso, valores[0], valores[1], valores[2], valores[3], valores[4]],
+            )?;
+        }
+        inserted += novo;
     }
 
     tx.commit()?;
-    println!("Ingestão concluída. {} linhas inseridas.", inserted);
-    Ok(())
-}
\ No newline at end of file
+    Ok(inserted)
+}
@@ -0,0 +1,127 @@
+use rusqlite::types::Type;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+
+use crate::core::mega_sena::Bet;
+use crate::core::periodo::Periodo;
+use crate::error::{Error, Result};
+
+/// Quais lotes gravados consultar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelecaoLotes {
+    /// O lote gravado mais recentemente.
+    Ultimo,
+    Ids(Vec<i64>),
+    /// Lotes criados dentro do período.
+    Periodo(Periodo),
+    /// Lotes marcados para a Mega da Virada deste ano.
+    Virada(i32),
+}
+
+/// Um lote gravado em `t_lote`, com as apostas de `t_aposta`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Lote {
+    pub id: i64,
+    pub uuid: String,
+    /// Como gravado pelo SQLite (`yyyy-mm-dd hh:mm:ss`, em UTC).
+    pub criado_em: Option<String>,
+    /// Nome da estratégia; ausente em lotes anteriores à migration 0011.
+    pub strategy: Option<String>,
+    /// A `GeneratorConfig` gravada, como JSON, para não perder campos de
+    /// versões antigas ou futuras.
+    pub config: Option<serde_json::Value>,
+    /// Ano da Mega da Virada para a qual o lote foi gerado.
+    pub virada: Option<i32>,
+    pub apostas: Vec<Aposta>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Aposta {
+    pub id: i64,
+    pub numeros: Bet,
+}
+
+/// Lê os lotes selecionados, em ordem de id. Ids inexistentes são erro.
+pub fn listar(conn: &Connection, selecao: &SelecaoLotes) -> Result<Vec<Lote>> {
+    let ids: Vec<i64> = match selecao {
+        SelecaoLotes::Ultimo => {
+            let mut stmt = conn.prepare("SELECT id FROM t_lote ORDER BY id DESC LIMIT 1")?;
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?
+        }
+        SelecaoLotes::Ids(ids) => ids.clone(),
+        SelecaoLotes::Periodo(periodo) => {
+            let mut stmt = conn.prepare(
+                "SELECT id FROM t_lote
+                 WHERE (?1 IS NULL OR date(created_at) >= ?1)
+                   AND (?2 IS NULL OR date(created_at) <= ?2)
+                 ORDER BY id ASC",
+            )?;
+            stmt.query_map(params![periodo.de, periodo.ate], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?
+        }
+        SelecaoLotes::Virada(ano) => {
+            let mut stmt = conn.prepare("SELECT id FROM t_lote WHERE virada = ?1 ORDER BY id")?;
+            stmt.query_map([ano], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?
+        }
+    };
+
+    ids.into_iter()
+        .map(|id| {
+            ler_lote(conn, id)?.ok_or_else(|| Error::Config(format!("lote {} não encontrado", id)))
+        })
+        .collect()
+}
+
+/// Marca o lote como gerado para a Mega da Virada de `ano`.
+pub fn marcar_virada(conn: &Connection, id: i64, ano: i32) -> Result<()> {
+    let alterados = conn.execute(
+        "UPDATE t_lote SET virada = ?2 WHERE id = ?1",
+        params![id, ano],
+    )?;
+    if alterados == 0 {
+        return Err(Error::Config(format!("lote {} não encontrado", id)));
+    }
+    Ok(())
+}
+
+fn ler_lote(conn: &Connection, id: i64) -> Result<Option<Lote>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, uuid, created_at, strategy, config, virada FROM t_lote WHERE id = ?1",
+    )?;
+    let mut rows = stmt.query([id])?;
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+
+    let config = row
+        .get::<_, Option<String>>(4)?
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, Type::Text, Box::new(e)))?;
+    let mut lote = Lote {
+        id: row.get(0)?,
+        uuid: row.get(1)?,
+        criado_em: row.get(2)?,
+        strategy: row.get(3)?,
+        config,
+        virada: row.get(5)?,
+        apostas: Vec::new(),
+    };
+
+    let mut stmt =
+        conn.prepare("SELECT id, numeros FROM t_aposta WHERE lote_id = ?1 ORDER BY id")?;
+    let apostas = stmt.query_map([id], |row| {
+        let numeros: String = row.get(1)?;
+        Ok(Aposta {
+            id: row.get(0)?,
+            numeros: numeros.parse().map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(1, Type::Text, Box::new(e))
+            })?,
+        })
+    })?;
+    lote.apostas = apostas.collect::<rusqlite::Result<_>>()?;
+
+    Ok(Some(lote))
+}
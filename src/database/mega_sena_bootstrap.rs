@@ -1,33 +1,31 @@
 use std::path::Path;
-use rusqlite::{params, Connection, OptionalExtension};
-use crate::{database};
-
-pub fn bootstrap_mega_sena_data_from_csv() -> anyhow::Result<()> {
-
-    let db_path = "mega_sena.db";
-    let csv_mega_sena_path = "mega_sena.csv";
-
-    let mut conn = Connection::open(db_path)?;
-    println!("Conectado ao SQLite em {}", db_path);
-
+use rusqlite::{Connection, OptionalExtension};
+use crate::database;
+use crate::error::Result;
+
+/// O que o bootstrap fez; quem chama decide o que mostrar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ingestao {
+    /// A tabela de concursos já tinha dados; o CSV nem foi lido.
+    JaPopulado,
+    /// O CSV não existe; nada foi importado.
+    SemArquivo,
+    /// Quantidade de concursos importados do CSV.
+    Importados(usize),
+}
 
-    if !mega_sena_table_has_data(&conn)? {
-        if Path::new(csv_mega_sena_path).exists() {
-            println!("Iniciando ingestão do CSV '{}'", csv_mega_sena_path);
-            database::csv::ingest_csv_mega_sena_to_sqlite(&mut conn, csv_mega_sena_path)?;
-        } else {
-            println!(
-                "Arquivo CSV '{}' não encontrado — pulando ingestão.",
-                csv_mega_sena_path
-            );
-        }
+pub fn bootstrap_mega_sena_data_from_csv(conn: &mut Connection, csv_mega_sena_path: &Path) -> Result<Ingestao> {
+    if mega_sena_table_has_data(conn)? {
+        return Ok(Ingestao::JaPopulado);
+    }
+    if !csv_mega_sena_path.exists() {
+        return Ok(Ingestao::SemArquivo);
     }
-    return Ok(());
+    let importados = database::csv::ingest_csv_mega_sena_to_sqlite(conn, csv_mega_sena_path)?;
+    Ok(Ingestao::Importados(importados))
 }
 
-pub fn mega_sena_table_has_data(
-    conn: &Connection,
-) -> Result<bool, rusqlite::Error> {
+pub fn mega_sena_table_has_data(conn: &Connection) -> Result<bool> {
     let sql = "
         SELECT id
         FROM t_mega_sena
@@ -44,4 +42,4 @@ pub fn mega_sena_table_has_data(
 
 
     Ok(false)
-}
\ No newline at end of file
+}
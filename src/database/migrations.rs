@@ -1,20 +1,149 @@
+use crate::database::schema::{self, SchemaChange};
+use crate::database::snapshot::{self, SnapshotPolicy};
+use crate::database::sqlite;
+use crate::error::{Error, Result};
 use crate::shared::sha3;
-use anyhow::{Context, Result};
-use chrono::Utc;
-use rand::seq::IteratorRandom;
-use rusqlite::{Connection, OptionalExtension, params};
-use std::io::{BufReader, Read};
-use std::path::Path;
-use std::{fs, num};
+use rusqlite::{Connection, params};
+use std::path::{Path, PathBuf};
+
+/// Uma migration SQL embutida no binário em tempo de compilação, com o
+/// script `.down.sql` opcional que a desfaz.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub file_name: &'static str,
+    pub sql: &'static str,
+    pub down: Option<&'static str>,
+}
+
+impl Migration {
+    /// SHA3-256 do conteúdo, igual ao de `sha3_256_of_file` sobre o arquivo.
+    pub fn checksum(&self) -> String {
+        sha3::sha3_256_of_bytes(self.sql.as_bytes())
+    }
+}
+
+macro_rules! migration {
+    ($file:literal) => {
+        Migration {
+            file_name: $file,
+            sql: include_str!(concat!("../../migrations/", $file)),
+            down: None,
+        }
+    };
+    ($file:literal, down = $down:literal) => {
+        Migration {
+            file_name: $file,
+            sql: include_str!(concat!("../../migrations/", $file)),
+            down: Some(include_str!(concat!("../../migrations/", $down))),
+        }
+    };
+}
+
+/// Todas as migrations, em ordem de execução. Um arquivo novo em
+/// `migrations/` (e seu `.down.sql`, se houver) precisa ser adicionado aqui.
+pub static MIGRATIONS: &[Migration] = &[
+    migration!("0000_init.sql"),
+    migration!("0001_generated_jogos.sql"),
+    migration!("0002_add_tabela_lotofacil.sql"),
+    migration!("0003_add_indexes.sql"),
+    migration!("0004_reboot_mega_sena_generation.sql"),
+    migration!("0005_add_data_typesafe_column.sql"),
+    // Sem down: sem a coluna generated_by_rust as apostas devolvidas por
+    // 0009_create_lote_aposta.down.sql virariam concursos oficiais.
+    migration!("0006_add_column_generated_games.sql"),
+    migration!(
+        "0007_add_mission_control_table.sql",
+        down = "0007_add_mission_control_table.down.sql"
+    ),
+    migration!(
+        "0008_backfill_data_typesafe.sql",
+        down = "0008_backfill_data_typesafe.down.sql"
+    ),
+    migration!(
+        "0009_create_lote_aposta.sql",
+        down = "0009_create_lote_aposta.down.sql"
+    ),
+    migration!(
+        "0010_add_rateio_columns.sql",
+        down = "0010_add_rateio_columns.down.sql"
+    ),
+    migration!(
+        "0011_add_lote_strategy.sql",
+        down = "0011_add_lote_strategy.down.sql"
+    ),
+    migration!(
+        "0012_add_mega_da_virada.sql",
+        down = "0012_add_mega_da_virada.down.sql"
+    ),
+    migration!(
+        "0013_unique_concurso.sql",
+        down = "0013_unique_concurso.down.sql"
+    ),
+];
+
+/// Registro de uma migration já executada, como está em `t_migration`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub file_name: String,
+    pub checksum_sha3: String,
+    pub executed_at: String,
+}
+
+/// Situação de uma migration em relação ao banco.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Executada e com checksum igual ao embutido.
+    Applied { executed_at: String },
+    /// Ainda não executada.
+    Pending,
+    /// Executada, mas o SQL embutido mudou desde então.
+    ChecksumMismatch { stored: String, current: String },
+    /// Registrada no banco, mas desconhecida por este binário.
+    Unknown { executed_at: String },
+}
+
+/// Como tratar migrations aplicadas cujo checksum mudou desde a execução.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DriftMode {
+    /// Divergência é erro. Indicado para CI e bancos compartilhados.
+    #[default]
+    Strict,
+    /// Divergência vira aviso e as pendentes continuam sendo aplicadas.
+    /// Indicado para desenvolvimento local.
+    Lenient,
+}
+
+/// Checksums de uma migration cujo conteúdo mudou depois de aplicada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    pub file_name: String,
+    /// Checksum gravado em `t_migration` quando a migration foi executada.
+    pub stored: String,
+    /// Checksum do SQL embutido neste binário.
+    pub embedded: String,
+    /// Checksum do arquivo em disco, quando o diretório de migrations existe.
+    pub on_disk: Option<String>,
+}
+
+impl Drift {
+    /// O banco diverge do binário: é o que `migrate repair` corrige.
+    pub fn is_recorded(&self) -> bool {
+        self.stored != self.embedded
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub file_name: String,
+    pub state: MigrationState,
+}
 
 pub fn check_migration_table_exists(conn: &Connection) -> Result<bool> {
-    let exists: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='t_migration'",
-            [],
-            |row| row.get(0),
-        )
-        .context("Erro ao checar tabela t_migration")?;
+    let exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='t_migration'",
+        [],
+        |row| row.get(0),
+    )?;
     Ok(exists > 0)
 }
 
@@ -31,58 +160,321 @@ pub fn create_migration_table(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
-pub fn run_migrations(conn: &Connection) -> Result<()> {
-    let migrations_dir = Path::new("./migrations");
-    if !migrations_dir.exists() {
-        println!("Diretório ./migrations não encontrado — pulando migrations.");
-        return Ok(());
+/// Migrations registradas em `t_migration`, em ordem de execução.
+pub fn applied_migrations(conn: &Connection) -> Result<Vec<AppliedMigration>> {
+    if !check_migration_table_exists(conn)? {
+        return Ok(Vec::new());
     }
 
-    let mut entries: Vec<_> = fs::read_dir(migrations_dir)?
-        .filter_map(|e| e.ok())
+    let mut stmt = conn
+        .prepare("SELECT file_name, checksum_sha3, executed_at FROM t_migration ORDER BY id ASC")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(AppliedMigration {
+            file_name: row.get(0)?,
+            checksum_sha3: row.get(1)?,
+            executed_at: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Situação de cada migration embutida e de registros desconhecidos no banco.
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    let applied = applied_migrations(conn)?;
+
+    let mut result: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|m| {
+            let state = match applied.iter().find(|a| a.file_name == m.file_name) {
+                None => MigrationState::Pending,
+                Some(a) => {
+                    let current = m.checksum();
+                    if a.checksum_sha3 == current {
+                        MigrationState::Applied {
+                            executed_at: a.executed_at.clone(),
+                        }
+                    } else {
+                        MigrationState::ChecksumMismatch {
+                            stored: a.checksum_sha3.clone(),
+                            current,
+                        }
+                    }
+                }
+            };
+            MigrationStatus {
+                file_name: m.file_name.to_string(),
+                state,
+            }
+        })
         .collect();
-    entries.sort_by_key(|e| e.file_name());
 
-    for entry in entries {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) != Some("sql") {
-            continue;
+    result.extend(
+        applied
+            .into_iter()
+            .filter(|a| !MIGRATIONS.iter().any(|m| m.file_name == a.file_name))
+            .map(|a| MigrationStatus {
+                file_name: a.file_name,
+                state: MigrationState::Unknown {
+                    executed_at: a.executed_at,
+                },
+            }),
+    );
+
+    Ok(result)
+}
+
+fn drift_error(file_name: &str, stored: &str, current: &str) -> Error {
+    Error::migration(
+        file_name,
+        format!(
+            "o checksum SHA3 do arquivo não corresponde ao registrado no banco \
+             (banco {}, atual {}); revise a alteração e use `migrate repair` para aceitá-la",
+            stored, current
+        ),
+    )
+}
+
+/// Confere os checksums das migrations executadas e falha na primeira divergência.
+pub fn verify(conn: &Connection) -> Result<()> {
+    for s in status(conn)? {
+        if let MigrationState::ChecksumMismatch { stored, current } = s.state {
+            return Err(drift_error(&s.file_name, &stored, &current));
         }
-        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
-
-        println!("Migration encontrada: {}", file_name);
-        let file_sha3 = sha3::sha3_256_of_file(&path)?;
-
-        let maybe_db_sha3: Option<String> = conn
-            .query_row(
-                "SELECT checksum_sha3 FROM t_migration WHERE file_name = ?1",
-                params![file_name],
-                |row| row.get(0),
-            )
-            .optional()?;
-
-        if let Some(db_sha3) = maybe_db_sha3 {
-            if db_sha3 != file_sha3 {
-                panic!(
-                    "O checksum SHA3 do arquivo {} não corresponde ao registrado no banco.",
-                    file_name
-                );
-            } else {
-                println!("Já executado e verificado: {}", file_name);
-                continue;
-            }
+    }
+    Ok(())
+}
+
+/// Relatório de divergência de checksums das migrations aplicadas.
+///
+/// Compara o valor gravado no banco com o SQL embutido e, se `migrations_dir`
+/// for informado e existir, com o arquivo em disco (via `sha3_256_of_file`),
+/// o que revela arquivos editados que ainda não foram recompilados.
+pub fn drift_report(conn: &Connection, migrations_dir: Option<&Path>) -> Result<Vec<Drift>> {
+    let dir = migrations_dir.filter(|d| d.is_dir());
+    let mut report = Vec::new();
+
+    for applied in applied_migrations(conn)? {
+        let Some(migration) = MIGRATIONS.iter().find(|m| m.file_name == applied.file_name) else {
+            continue;
+        };
+
+        let embedded = migration.checksum();
+        let on_disk = match dir.map(|d| d.join(migration.file_name)) {
+            Some(path) if path.exists() => Some(
+                sha3::sha3_256_of_file(&path)
+                    .map_err(|e| Error::migration(migration.file_name, e))?,
+            ),
+            _ => None,
+        };
+
+        let diverge = applied.checksum_sha3 != embedded
+            || on_disk
+                .as_ref()
+                .is_some_and(|d| *d != applied.checksum_sha3);
+        if diverge {
+            report.push(Drift {
+                file_name: applied.file_name,
+                stored: applied.checksum_sha3,
+                embedded,
+                on_disk,
+            });
         }
+    }
 
-        let sql_content = fs::read_to_string(&path)?;
-        conn.execute_batch(&sql_content)?;
+    Ok(report)
+}
+
+/// Regrava em `t_migration` o checksum do SQL embutido para as migrations
+/// divergentes, aceitando as alterações. Não executa nenhum SQL de migration.
+/// Devolve os nomes corrigidos.
+pub fn repair(conn: &Connection) -> Result<Vec<String>> {
+    let tx = conn.unchecked_transaction()?;
+    let mut corrigidas = Vec::new();
 
-        conn.execute(
-            "INSERT INTO t_migration (file_name, checksum_sha3) VALUES (?1, ?2)",
-            params![file_name, file_sha3],
+    for drift in drift_report(&tx, None)?
+        .into_iter()
+        .filter(Drift::is_recorded)
+    {
+        tx.execute(
+            "UPDATE t_migration SET checksum_sha3 = ?1 WHERE file_name = ?2",
+            params![drift.embedded, drift.file_name],
         )?;
+        corrigidas.push(drift.file_name);
+    }
+
+    tx.commit()?;
+    Ok(corrigidas)
+}
+
+fn find_migration(file_name: &str) -> Result<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .find(|m| m.file_name == file_name)
+        .ok_or_else(|| Error::migration(file_name, "migration desconhecida"))
+}
+
+/// Executa todas as migrations pendentes em modo estrito.
+pub fn run_migrations(conn: &Connection) -> Result<()> {
+    migrate_up(conn, None, DriftMode::Strict)?;
+    Ok(())
+}
+
+/// Resultado de [`migrate_up_with_snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationRun {
+    /// Snapshot gravado antes das migrations, quando havia alguma pendente.
+    pub snapshot: Option<PathBuf>,
+    /// Migrations executadas, em ordem.
+    pub executadas: Vec<&'static str>,
+}
+
+/// Como [`migrate_up`], mas antes de executar qualquer migration grava um
+/// snapshot do banco conforme `policy`. Bancos em memória não têm snapshot.
+pub fn migrate_up_with_snapshot(
+    conn: &Connection,
+    to: Option<&str>,
+    mode: DriftMode,
+    policy: &SnapshotPolicy,
+) -> Result<MigrationRun> {
+    let em_arquivo = conn.path().is_some_and(|p| !p.is_empty());
+    let snapshot = if em_arquivo && has_pending(conn, to)? {
+        Some(snapshot::take(conn, policy, "pre-migration")?)
+    } else {
+        None
+    };
+    Ok(MigrationRun {
+        snapshot,
+        executadas: migrate_up(conn, to, mode)?,
+    })
+}
+
+/// Se há migrations pendentes até `to` (inclusive), ou até o fim.
+fn has_pending(conn: &Connection, to: Option<&str>) -> Result<bool> {
+    for s in status(conn)? {
+        if s.state == MigrationState::Pending {
+            return Ok(true);
+        }
+        if to == Some(s.file_name.as_str()) {
+            break;
+        }
+    }
+    Ok(false)
+}
+
+/// Executa as migrations pendentes em ordem, parando depois de `to` (inclusive)
+/// quando informado. Devolve os nomes das migrations executadas, sem
+/// imprimir nada: quem chama decide o que mostrar.
+pub fn migrate_up(
+    conn: &Connection,
+    to: Option<&str>,
+    mode: DriftMode,
+) -> Result<Vec<&'static str>> {
+    let limite = match to {
+        Some(file_name) => Some(find_migration(file_name)?.file_name),
+        None => None,
+    };
+
+    create_migration_table(conn)?;
+    let applied = applied_migrations(conn)?;
+    let mut executadas = Vec::new();
+
+    for migration in MIGRATIONS {
+        let file_name = migration.file_name;
+        let checksum = migration.checksum();
 
-        println!("Executada migration: {}", file_name);
+        match applied.iter().find(|a| a.file_name == file_name) {
+            Some(a) if a.checksum_sha3 != checksum => match mode {
+                DriftMode::Strict => {
+                    return Err(drift_error(file_name, &a.checksum_sha3, &checksum));
+                }
+                // Quem chama avisa, via `drift_report`.
+                DriftMode::Lenient => {}
+            },
+            Some(_) => {}
+            None => {
+                apply(conn, migration, &checksum)?;
+                executadas.push(file_name);
+            }
+        }
+
+        if limite == Some(file_name) {
+            break;
+        }
     }
 
+    Ok(executadas)
+}
+
+/// Executa o SQL da migration e registra em `t_migration` numa única
+/// transação: se qualquer comando falhar, nada é aplicado.
+pub fn apply(conn: &Connection, migration: &Migration, checksum: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute_batch(migration.sql)
+        .map_err(|e| Error::migration(migration.file_name, e))?;
+    tx.execute(
+        "INSERT INTO t_migration (file_name, checksum_sha3) VALUES (?1, ?2)",
+        params![migration.file_name, checksum],
+    )?;
+
+    tx.commit()?;
     Ok(())
 }
+
+/// Desfaz as últimas `n` migrations aplicadas, da mais recente para a mais
+/// antiga, cada uma em sua própria transação. Falha antes de alterar o banco
+/// se alguma delas não tiver script `.down.sql`. Devolve os nomes desfeitos.
+pub fn rollback(conn: &Connection, n: usize) -> Result<Vec<String>> {
+    let mut alvo = applied_migrations(conn)?;
+    alvo.reverse();
+    alvo.truncate(n);
+
+    let mut planos = Vec::with_capacity(alvo.len());
+    for applied in &alvo {
+        let migration = find_migration(&applied.file_name)?;
+        let down = migration.down.ok_or_else(|| {
+            Error::migration(migration.file_name, "migration sem script .down.sql")
+        })?;
+        planos.push((migration.file_name, down));
+    }
+
+    let mut desfeitas = Vec::with_capacity(planos.len());
+    for (file_name, down) in planos {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(down)
+            .map_err(|e| Error::migration(file_name, e))?;
+        tx.execute(
+            "DELETE FROM t_migration WHERE file_name = ?1",
+            params![file_name],
+        )?;
+        tx.commit()?;
+        desfeitas.push(file_name.to_string());
+    }
+
+    Ok(desfeitas)
+}
+
+/// Resultado de um ensaio de migrations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRun {
+    /// Migrations que seriam executadas, em ordem.
+    pub executadas: Vec<&'static str>,
+    /// Diferenças de schema resultantes.
+    pub mudancas: Vec<SchemaChange>,
+}
+
+/// Aplica as migrations pendentes numa cópia em memória do banco e relata
+/// as mudanças de schema, sem tocar no arquivo original. Falha no primeiro
+/// comando com erro, assim como `migrate_up` falharia.
+pub fn dry_run(conn: &Connection, to: Option<&str>, mode: DriftMode) -> Result<DryRun> {
+    let copia = sqlite::copy_to_memory(conn)?;
+    let antes = schema::introspect(&copia)?;
+
+    let executadas = migrate_up(&copia, to, mode)?;
+
+    let depois = schema::introspect(&copia)?;
+    Ok(DryRun {
+        executadas,
+        mudancas: schema::diff(&antes, &depois),
+    })
+}
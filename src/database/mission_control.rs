@@ -0,0 +1,150 @@
+use core::fmt;
+use std::str::FromStr;
+
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::error::{Error, Result};
+
+/// Feature flags conhecidas, guardadas em `t_mission_control`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flag {
+    /// Imprime cada jogo bloqueado pelo histórico durante a geração.
+    PrintNaoJogavel,
+    /// Experimental: rejeita jogos com todas as bolas pares ou todas ímpares.
+    FiltroParidade,
+}
+
+impl Flag {
+    pub const ALL: &'static [Flag] = &[Flag::PrintNaoJogavel, Flag::FiltroParidade];
+
+    /// Valor de `key` na tabela.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::PrintNaoJogavel => "print_nao_jogavel",
+            Self::FiltroParidade => "filtro_paridade",
+        }
+    }
+
+    /// Valor usado quando a flag não está na tabela.
+    pub fn default_value(self) -> bool {
+        match self {
+            Self::PrintNaoJogavel => false,
+            Self::FiltroParidade => false,
+        }
+    }
+
+    pub fn descricao(self) -> &'static str {
+        match self {
+            Self::PrintNaoJogavel => "imprime os jogos bloqueados pelo histórico",
+            Self::FiltroParidade => "(experimental) rejeita jogos só com pares ou só com ímpares",
+        }
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for Flag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Flag::ALL
+            .iter()
+            .copied()
+            .find(|f| f.key() == s.trim())
+            .ok_or_else(|| Error::Config(format!("feature flag desconhecida: {}", s.trim())))
+    }
+}
+
+/// Situação de uma flag: valor efetivo e se veio do banco ou do padrão.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagState {
+    pub key: String,
+    pub flag: Option<Flag>,
+    pub enabled: bool,
+    pub from_database: bool,
+}
+
+/// Interpreta a coluna `enable`, que não tem tipo declarado na tabela.
+fn as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Null => None,
+        Value::Integer(i) => Some(*i != 0),
+        Value::Real(r) => Some(*r != 0.0),
+        Value::Text(t) => match t.trim().to_lowercase().as_str() {
+            "1" | "true" | "t" | "on" | "yes" | "s" | "sim" => Some(true),
+            "0" | "false" | "f" | "off" | "no" | "n" | "nao" | "não" => Some(false),
+            _ => None,
+        },
+        Value::Blob(_) => None,
+    }
+}
+
+/// Valor gravado para a chave (o registro mais recente vale), se houver.
+fn stored(conn: &Connection, key: &str) -> Result<Option<bool>> {
+    let value: Option<Value> = conn
+        .query_row(
+            "SELECT enable FROM t_mission_control WHERE key = ?1 ORDER BY id DESC LIMIT 1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(value.as_ref().and_then(as_bool))
+}
+
+/// Valor efetivo da flag: o do banco ou, na ausência, o padrão.
+pub fn is_enabled(conn: &Connection, flag: Flag) -> Result<bool> {
+    Ok(stored(conn, flag.key())?.unwrap_or(flag.default_value()))
+}
+
+/// Liga ou desliga a flag, atualizando o registro existente ou criando um.
+pub fn set(conn: &Connection, flag: Flag, enabled: bool) -> Result<()> {
+    let atualizados = conn.execute(
+        "UPDATE t_mission_control SET enable = ?1 WHERE key = ?2",
+        params![enabled, flag.key()],
+    )?;
+    if atualizados == 0 {
+        conn.execute(
+            "INSERT INTO t_mission_control (key, enable, generated_by_rust) VALUES (?1, ?2, TRUE)",
+            params![flag.key(), enabled],
+        )?;
+    }
+    Ok(())
+}
+
+/// Todas as flags conhecidas e também chaves desconhecidas presentes na tabela.
+pub fn list(conn: &Connection) -> Result<Vec<FlagState>> {
+    let mut estados = Vec::new();
+    for &flag in Flag::ALL {
+        let gravado = stored(conn, flag.key())?;
+        estados.push(FlagState {
+            key: flag.key().to_string(),
+            flag: Some(flag),
+            enabled: gravado.unwrap_or(flag.default_value()),
+            from_database: gravado.is_some(),
+        });
+    }
+
+    let desconhecidas: Vec<String> = conn
+        .prepare("SELECT DISTINCT key FROM t_mission_control ORDER BY key")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?
+        .into_iter()
+        .filter(|k| !Flag::ALL.iter().any(|f| f.key() == k))
+        .collect();
+    for key in desconhecidas {
+        let gravado = stored(conn, &key)?;
+        estados.push(FlagState {
+            key,
+            flag: None,
+            enabled: gravado.unwrap_or(false),
+            from_database: gravado.is_some(),
+        });
+    }
+
+    Ok(estados)
+}
@@ -1,4 +1,8 @@
 pub mod csv;
+pub mod lotes;
+pub(crate) mod mega_sena_bootstrap;
 pub mod migrations;
+pub mod mission_control;
+pub mod schema;
+pub mod snapshot;
 pub mod sqlite;
-pub(crate) mod mega_sena_bootstrap;
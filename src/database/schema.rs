@@ -0,0 +1,249 @@
+use core::fmt;
+use std::collections::BTreeMap;
+
+use rusqlite::Connection;
+
+use crate::error::Result;
+
+/// Uma coluna como descrita por `PRAGMA table_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub tipo: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    pub primary_key: bool,
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.tipo.is_empty() {
+            write!(f, " {}", self.tipo)?;
+        }
+        if self.primary_key {
+            write!(f, " PRIMARY KEY")?;
+        }
+        if self.not_null {
+            write!(f, " NOT NULL")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {}", default)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// Tabelas, colunas e índices criados pelo usuário (ignora `sqlite_*`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    pub tables: BTreeMap<String, Vec<Column>>,
+    pub indexes: BTreeMap<String, Index>,
+}
+
+/// Uma diferença entre dois schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    TableAdded {
+        table: String,
+        columns: Vec<Column>,
+    },
+    TableDropped {
+        table: String,
+    },
+    ColumnAdded {
+        table: String,
+        column: Column,
+    },
+    ColumnDropped {
+        table: String,
+        column: String,
+    },
+    ColumnChanged {
+        table: String,
+        before: Column,
+        after: Column,
+    },
+    IndexAdded {
+        name: String,
+        index: Index,
+    },
+    IndexDropped {
+        name: String,
+        table: String,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TableAdded { table, columns } => {
+                let cols: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+                write!(f, "+ tabela {} ({})", table, cols.join(", "))
+            }
+            Self::TableDropped { table } => write!(f, "- tabela {}", table),
+            Self::ColumnAdded { table, column } => write!(f, "+ coluna {}.{}", table, column),
+            Self::ColumnDropped { table, column } => write!(f, "- coluna {}.{}", table, column),
+            Self::ColumnChanged {
+                table,
+                before,
+                after,
+            } => write!(f, "~ coluna {}.{} -> {}", table, before, after),
+            Self::IndexAdded { name, index } => write!(
+                f,
+                "+ índice {}{} em {}({})",
+                if index.unique { "único " } else { "" },
+                name,
+                index.table,
+                index.columns.join(", ")
+            ),
+            Self::IndexDropped { name, table } => write!(f, "- índice {} em {}", name, table),
+        }
+    }
+}
+
+/// Lê o schema atual do banco.
+pub fn introspect(conn: &Connection) -> Result<Schema> {
+    let mut schema = Schema::default();
+
+    let tables: Vec<String> = conn
+        .prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+             ORDER BY name",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    for table in tables {
+        let columns = conn
+            .prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)")?
+            .query_map([&table], |row| {
+                Ok(Column {
+                    name: row.get(0)?,
+                    tipo: row.get(1)?,
+                    not_null: row.get(2)?,
+                    default: row.get(3)?,
+                    primary_key: row.get::<_, i64>(4)? > 0,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        schema.tables.insert(table, columns);
+    }
+
+    let indexes: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT name, tbl_name FROM sqlite_master
+             WHERE type = 'index' AND name NOT LIKE 'sqlite_%'
+             ORDER BY name",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    for (name, table) in indexes {
+        let columns = conn
+            .prepare("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")?
+            .query_map([&name], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        let unique: bool = conn.query_row(
+            "SELECT \"unique\" FROM pragma_index_list(?1) WHERE name = ?2",
+            [&table, &name],
+            |row| row.get(0),
+        )?;
+        schema.indexes.insert(
+            name,
+            Index {
+                table,
+                columns,
+                unique,
+            },
+        );
+    }
+
+    Ok(schema)
+}
+
+/// Diferenças para ir de `before` a `after`: tabelas, colunas e índices.
+pub fn diff(before: &Schema, after: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    for (table, columns) in &before.tables {
+        let Some(novas) = after.tables.get(table) else {
+            changes.push(SchemaChange::TableDropped {
+                table: table.clone(),
+            });
+            continue;
+        };
+
+        for column in columns {
+            match novas.iter().find(|c| c.name == column.name) {
+                None => changes.push(SchemaChange::ColumnDropped {
+                    table: table.clone(),
+                    column: column.name.clone(),
+                }),
+                Some(nova) if nova != column => changes.push(SchemaChange::ColumnChanged {
+                    table: table.clone(),
+                    before: column.clone(),
+                    after: nova.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for nova in novas {
+            if !columns.iter().any(|c| c.name == nova.name) {
+                changes.push(SchemaChange::ColumnAdded {
+                    table: table.clone(),
+                    column: nova.clone(),
+                });
+            }
+        }
+    }
+
+    for (table, columns) in &after.tables {
+        if !before.tables.contains_key(table) {
+            changes.push(SchemaChange::TableAdded {
+                table: table.clone(),
+                columns: columns.clone(),
+            });
+        }
+    }
+
+    for (name, index) in &before.indexes {
+        match after.indexes.get(name) {
+            Some(novo) if novo == index => {}
+            Some(novo) => {
+                changes.push(SchemaChange::IndexDropped {
+                    name: name.clone(),
+                    table: index.table.clone(),
+                });
+                changes.push(SchemaChange::IndexAdded {
+                    name: name.clone(),
+                    index: novo.clone(),
+                });
+            }
+            None => changes.push(SchemaChange::IndexDropped {
+                name: name.clone(),
+                table: index.table.clone(),
+            }),
+        }
+    }
+
+    for (name, index) in &after.indexes {
+        if !before.indexes.contains_key(name) {
+            changes.push(SchemaChange::IndexAdded {
+                name: name.clone(),
+                index: index.clone(),
+            });
+        }
+    }
+
+    changes
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::database::sqlite;
+use crate::error::Result;
+
+/// Onde guardar snapshots automáticos e quantos manter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotPolicy {
+    pub dir: PathBuf,
+    /// Quantidade de snapshots mantidos; os mais antigos são apagados.
+    pub keep: usize,
+}
+
+impl SnapshotPolicy {
+    /// Diretório `snapshots/` ao lado do banco.
+    pub fn beside(db_path: &Path, keep: usize) -> Self {
+        let dir = db_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join("snapshots");
+        Self { dir, keep }
+    }
+}
+
+/// Nome-base dos snapshots de um banco: o nome do arquivo sem extensão.
+fn prefixo(conn: &Connection) -> String {
+    conn.path()
+        .filter(|p| !p.is_empty())
+        .and_then(|p| {
+            Path::new(p)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "memoria".to_string())
+}
+
+/// Tira um snapshot do banco em `policy.dir` e apaga os excedentes, sem
+/// nunca apagar o recém-criado, mesmo com `keep` zero.
+/// O nome inclui data/hora e `motivo` (ex.: `pre-migration`).
+pub fn take(conn: &Connection, policy: &SnapshotPolicy, motivo: &str) -> Result<PathBuf> {
+    fs::create_dir_all(&policy.dir)?;
+
+    let prefixo = prefixo(conn);
+    // Dois snapshots no mesmo milissegundo teriam o mesmo nome; espera o
+    // relógio avançar para não sobrescrever o anterior.
+    let destino = loop {
+        let agora = chrono::Local::now().format("%Y%m%dT%H%M%S%3f");
+        let destino = policy
+            .dir
+            .join(format!("{}-{}-{}.db", prefixo, agora, motivo));
+        if !destino.exists() {
+            break destino;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    };
+    sqlite::backup_to_file(conn, &destino)?;
+
+    prune(&policy.dir, &prefixo, policy.keep.max(1))?;
+    Ok(destino)
+}
+
+/// Snapshots de `prefixo` em `dir`, do mais antigo para o mais recente.
+pub fn list(dir: &Path, prefixo: &str) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == "db")
+                && p.file_name()
+                    .is_some_and(|n| e_snapshot_de(&n.to_string_lossy(), prefixo))
+        })
+        .collect();
+    // O carimbo de data/hora logo após o prefixo mantém a ordem cronológica.
+    snapshots.sort();
+    Ok(snapshots)
+}
+
+/// Se `nome` é um snapshot de `prefixo`: o prefixo, um hífen e o carimbo
+/// `AAAAMMDDT...`. Só o hífen não basta, senão os snapshots de
+/// `mega-sena.db` seriam tomados como de `mega.db`.
+fn e_snapshot_de(nome: &str, prefixo: &str) -> bool {
+    nome.strip_prefix(prefixo)
+        .and_then(|resto| resto.strip_prefix('-'))
+        .is_some_and(|carimbo| {
+            let carimbo = carimbo.as_bytes();
+            carimbo.len() > 8 && carimbo[..8].iter().all(u8::is_ascii_digit) && carimbo[8] == b'T'
+        })
+}
+
+/// Snapshots do banco aberto em `conn`.
+pub fn list_for(conn: &Connection, dir: &Path) -> Result<Vec<PathBuf>> {
+    list(dir, &prefixo(conn))
+}
+
+/// Apaga os snapshots mais antigos, mantendo os `keep` mais recentes.
+/// Devolve os caminhos apagados.
+pub fn prune(dir: &Path, prefixo: &str, keep: usize) -> Result<Vec<PathBuf>> {
+    let snapshots = list(dir, prefixo)?;
+    let excedentes = snapshots.len().saturating_sub(keep);
+
+    let mut apagados = Vec::with_capacity(excedentes);
+    for path in snapshots.into_iter().take(excedentes) {
+        fs::remove_file(&path)?;
+        apagados.push(path);
+    }
+    Ok(apagados)
+}
@@ -0,0 +1,39 @@
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::backup::{Backup, Progress};
+use rusqlite::{Connection, MAIN_DB};
+
+use crate::error::Result;
+
+/// Copia o banco inteiro para um banco em memória usando a API de backup
+/// online do SQLite. O original não é alterado.
+pub fn copy_to_memory(conn: &Connection) -> Result<Connection> {
+    let mut copia = Connection::open_in_memory()?;
+    {
+        let backup = Backup::new(conn, &mut copia)?;
+        backup.run_to_completion(256, Duration::ZERO, None)?;
+    }
+    Ok(copia)
+}
+
+/// Grava uma cópia consistente do banco em `destino` (API de backup online),
+/// mesmo com o banco em uso.
+pub fn backup_to_file(conn: &Connection, destino: &Path) -> Result<()> {
+    conn.backup(MAIN_DB, destino, None)?;
+    Ok(())
+}
+
+/// Substitui o conteúdo do banco pelo do arquivo `origem`.
+pub fn restore_from_file(conn: &mut Connection, origem: &Path) -> Result<()> {
+    if !origem.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("backup {} não encontrado", origem.display()),
+        )
+        .into());
+    }
+    conn.restore(MAIN_DB, origem, None::<fn(Progress)>)?;
+    Ok(())
+}
@@ -1,44 +1,50 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-use rusqlite::{Connection, OptionalExtension, params};
+use rusqlite::{Connection, OptionalExtension, ToSql, params};
+use serde::Serialize;
 
-use crate::core::{mega_sena::MegaSena};
+use crate::core::mega_sena::{Ball, Bet, Draw};
+use crate::core::periodo::Periodo;
+use crate::engine::conferencia::Rateio;
+use crate::engine::paralelo;
 
-/// Verifica se o jogo já existe no histórico
+/// Verifica se algum concurso do período está inteiramente contido na aposta
+/// (para apostas de 6 números: se o jogo já saiu).
 pub fn game_already_exists(
     conn: &Connection,
-    megasena: &MegaSena,
+    aposta: &Bet,
+    periodo: &Periodo,
 ) -> Result<bool, rusqlite::Error> {
-    let exists: bool = conn.query_row(
+    let placeholders = (3..3 + aposta.len())
+        .map(|i| format!("?{}", i))
+        .collect::<Vec<_>>()
+        .join(",");
+    let sql = format!(
         "SELECT EXISTS(
                 SELECT 1 FROM t_mega_sena
-                WHERE bola_1=?1 AND bola_2=?2 AND bola_3=?3
-                  AND bola_4=?4 AND bola_5=?5 AND bola_6=?6
+                WHERE bola_1 IN ({p}) AND bola_2 IN ({p}) AND bola_3 IN ({p})
+                  AND bola_4 IN ({p}) AND bola_5 IN ({p}) AND bola_6 IN ({p})
+                  AND (?1 IS NULL OR data_typesafe >= ?1)
+                  AND (?2 IS NULL OR data_typesafe <= ?2)
             )",
-        params![
-            megasena.bola_1,
-            megasena.bola_2,
-            megasena.bola_3,
-            megasena.bola_4,
-            megasena.bola_5,
-            megasena.bola_6,
-        ],
-        |row| row.get(0),
-    )?;
+        p = placeholders
+    );
 
-    Ok(exists)
-}
+    let mut values: Vec<&dyn ToSql> = vec![&periodo.de, &periodo.ate];
+    values.extend(aposta.bolas().iter().map(|b| b as &dyn ToSql));
 
-/// Gera todas as combinações de trios (C(6,3)=20) para um jogo de 6 números.
-/// O jogo deve ter exatamente 6 números.
-pub fn generate_trios(game: &[i64]) -> Vec<[i64; 3]> {
-    assert!(game.len() == 6, "O jogo deve conter exatamente 6 números!");
+    conn.query_row(&sql, values.as_slice(), |row| row.get(0))
+}
 
+/// Gera todas as combinações de trios da aposta (C(6,3)=20 para 6 números).
+pub fn generate_trios(aposta: &Bet) -> Vec<[Ball; 3]> {
+    let game = aposta.bolas();
+    let n = game.len();
     let mut trios = Vec::new();
 
-    for i in 0..4 {
-        for j in (i + 1)..5 {
-            for k in (j + 1)..6 {
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in (j + 1)..n {
                 trios.push([game[i], game[j], game[k]]);
             }
         }
@@ -51,17 +57,20 @@ pub fn generate_trios(game: &[i64]) -> Vec<[i64; 3]> {
 /// Retorna true se encontrar ao menos 1 ocorrência.
 pub fn has_repeated_trio_by_sets(
     conn: &Connection,
-    trios: &Vec<[i64; 3]>,
+    trios: &[[Ball; 3]],
+    periodo: &Periodo,
 ) -> Result<bool, rusqlite::Error> {
     let sql = "
         SELECT concurso
         FROM t_mega_sena
-        WHERE bola_1 IN (?1,?2,?3)
+        WHERE (bola_1 IN (?1,?2,?3)
            OR bola_2 IN (?1,?2,?3)
            OR bola_3 IN (?1,?2,?3)
            OR bola_4 IN (?1,?2,?3)
            OR bola_5 IN (?1,?2,?3)
-           OR bola_6 IN (?1,?2,?3)
+           OR bola_6 IN (?1,?2,?3))
+          AND (?4 IS NULL OR data_typesafe >= ?4)
+          AND (?5 IS NULL OR data_typesafe <= ?5)
         GROUP BY concurso
         HAVING COUNT(*) >= 3
         LIMIT 1;
@@ -69,7 +78,11 @@ pub fn has_repeated_trio_by_sets(
 
     for trio in trios {
         let exists: Option<i32> = conn
-            .query_row(sql, params![trio[0], trio[1], trio[2]], |row| row.get(0))
+            .query_row(
+                sql,
+                params![trio[0], trio[1], trio[2], periodo.de, periodo.ate],
+                |row| row.get(0),
+            )
             .optional()?;
 
         if exists.is_some() {
@@ -80,57 +93,77 @@ pub fn has_repeated_trio_by_sets(
     Ok(false)
 }
 
+/// Lista os concursos do histórico dentro do período, em ordem de concurso.
 pub fn listar_historico_mega_sena(
     conn: &Connection,
-) -> Result<Vec<MegaSena>, rusqlite::Error> {
+    periodo: &Periodo,
+) -> Result<Vec<Draw>, rusqlite::Error> {
     let mut stmt = conn.prepare(
-        "SELECT id, concurso, data,
+        "SELECT concurso, data, data_typesafe,
                 bola_1, bola_2, bola_3,
-                bola_4, bola_5, bola_6,
-                inserted_at
+                bola_4, bola_5, bola_6
          FROM t_mega_sena
+         WHERE (?1 IS NULL OR data_typesafe >= ?1)
+           AND (?2 IS NULL OR data_typesafe <= ?2)
          ORDER BY concurso ASC",
     )?;
 
-    let rows = stmt.query_map([], |row| {
-        // Coleta todas as bolas em um vetor
-        let bolas: [Option<i64>; 6] = [
-            row.get(3)?,
-            row.get(4)?,
-            row.get(5)?,
-            row.get(6)?,
-            row.get(7)?,
-            row.get(8)?,
-        ];
-
-        // Cria e preenche o HashSet
-        let mut set = HashSet::new();
-        for b in bolas {
-            if let Some(v) = b {
-                set.insert(v);
-            }
-        }
+    let rows = stmt.query_map(params![periodo.de, periodo.ate], |row| {
+        Draw::try_from(row)
+    })?;
 
-        Ok(MegaSena {
-            id: row.get(0)?,
-            concurso: row.get(1)?,
-            data: row.get(2)?,
-            bola_1: bolas[0],
-            bola_2: bolas[1],
-            bola_3: bolas[2],
-            bola_4: bolas[3],
-            bola_5: bolas[4],
-            bola_6: bolas[5],
-            inserted_at: row.get(9)?,
-            generated_by_rust: false,
-            set,
-        })
+    // Uma linha inválida é erro: descartá-la em silêncio distorceria as estatísticas.
+    rows.collect()
+}
+
+/// Rateio gravado de cada concurso do período que tenha alguma faixa preenchida.
+pub fn listar_rateios(
+    conn: &Connection,
+    periodo: &Periodo,
+) -> Result<HashMap<u32, Rateio>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT concurso, rateio_sena_centavos, rateio_quina_centavos, rateio_quadra_centavos
+         FROM t_mega_sena
+         WHERE (?1 IS NULL OR data_typesafe >= ?1)
+           AND (?2 IS NULL OR data_typesafe <= ?2)
+           AND COALESCE(rateio_sena_centavos, rateio_quina_centavos, rateio_quadra_centavos)
+               IS NOT NULL",
+    )?;
+
+    let rows = stmt.query_map(params![periodo.de, periodo.ate], |row| {
+        Ok((
+            row.get(0)?,
+            Rateio {
+                sena: row.get(1)?,
+                quina: row.get(2)?,
+                quadra: row.get(3)?,
+            },
+        ))
     })?;
 
-    // Coleta tudo para um Vec<HistoricoMegaSena>
-    let historico: Vec<MegaSena> = rows
-        .filter_map(|r| r.ok()) // descarta linhas com erro
-        .collect();
+    rows.collect()
+}
+
+/// Um concurso do histórico que compartilha bolas com uma aposta.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Coincidencia {
+    pub concurso: Draw,
+    pub bolas_em_comum: Vec<Ball>,
+}
 
-    Ok(historico)
+impl Coincidencia {
+    pub fn acertos(&self) -> usize {
+        self.bolas_em_comum.len()
+    }
+}
+
+/// Todos os concursos do histórico com pelo menos `minimo` bolas em comum
+/// com a aposta, em ordem de concurso. A varredura roda em paralelo.
+pub fn coincidencias(aposta: &Bet, historico: &[Draw], minimo: u32) -> Vec<Coincidencia> {
+    paralelo::varrer(historico, |d| {
+        (aposta.acertos(d) >= minimo).then(|| Coincidencia {
+            concurso: d.clone(),
+            bolas_em_comum: aposta.bolas_em_comum(d),
+        })
+    })
 }
@@ -0,0 +1,161 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Ball, Bet, Draw, combinacoes};
+use crate::engine::paralelo;
+
+/// Faixas de premiação da Mega-Sena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Faixa {
+    Sena,
+    Quina,
+    Quadra,
+}
+
+impl Faixa {
+    pub const ALL: [Faixa; 3] = [Faixa::Sena, Faixa::Quina, Faixa::Quadra];
+
+    pub fn acertos(self) -> u32 {
+        match self {
+            Self::Sena => 6,
+            Self::Quina => 5,
+            Self::Quadra => 4,
+        }
+    }
+}
+
+impl fmt::Display for Faixa {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Sena => "sena",
+            Self::Quina => "quina",
+            Self::Quadra => "quadra",
+        })
+    }
+}
+
+/// Valor pago por ganhador em cada faixa, em centavos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Rateio {
+    pub sena: Option<u64>,
+    pub quina: Option<u64>,
+    pub quadra: Option<u64>,
+}
+
+impl Rateio {
+    pub fn de(&self, faixa: Faixa) -> Option<u64> {
+        match faixa {
+            Faixa::Sena => self.sena,
+            Faixa::Quina => self.quina,
+            Faixa::Quadra => self.quadra,
+        }
+    }
+}
+
+/// O que a aposta teria ganho em um concurso.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Premiacao {
+    pub concurso: Draw,
+    pub bolas_em_comum: Vec<Ball>,
+    /// Prêmios por faixa; apostas com mais de seis números ganham vários.
+    pub premios: Vec<(Faixa, u64)>,
+    /// Valor total em centavos, se todas as faixas ganhas tiverem valor.
+    pub valor: Option<u64>,
+}
+
+impl Premiacao {
+    pub fn faixa(&self) -> Faixa {
+        self.premios[0].0
+    }
+}
+
+/// Resultado da conferência de uma aposta contra o histórico.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conferencia {
+    pub aposta: Bet,
+    pub concursos_conferidos: usize,
+    pub premiacoes: Vec<Premiacao>,
+    /// Total de prêmios por faixa, na ordem de [`Faixa::ALL`].
+    pub totais: Vec<(Faixa, u64)>,
+    /// Soma dos valores conhecidos, em centavos.
+    pub total: u64,
+    /// Premiações sem valor conhecido, que ficaram fora de `total`.
+    pub sem_valor: usize,
+}
+
+impl Conferencia {
+    /// Concursos cujas seis dezenas estão todas na aposta.
+    pub fn exatos(&self) -> impl Iterator<Item = &Premiacao> {
+        self.premiacoes.iter().filter(|p| p.faixa() == Faixa::Sena)
+    }
+}
+
+/// Quantos jogos simples de seis números contidos numa aposta de `tamanho`
+/// números, com `acertos` dezenas sorteadas, acertam exatamente a faixa.
+pub fn premios(tamanho: usize, acertos: u32, faixa: Faixa) -> u64 {
+    let (n, h, f) = (tamanho as u64, acertos as u64, faixa.acertos() as u64);
+    if h > n {
+        return 0;
+    }
+    combinacoes(h, f) * combinacoes(n - h, BOLAS_POR_SORTEIO as u64 - f)
+}
+
+/// Confere a aposta contra cada concurso, em paralelo. O valor de cada
+/// faixa vem de `rateios` e, se o concurso não tiver rateio gravado, de
+/// `padrao`.
+pub fn conferir(
+    aposta: &Bet,
+    historico: &[Draw],
+    rateios: &HashMap<u32, Rateio>,
+    padrao: &Rateio,
+) -> Conferencia {
+    let premiacoes: Vec<Premiacao> = paralelo::varrer(historico, |draw| {
+        let acertos = aposta.acertos(draw);
+        let premios: Vec<(Faixa, u64)> = Faixa::ALL
+            .iter()
+            .map(|&faixa| (faixa, premios(aposta.len(), acertos, faixa)))
+            .filter(|&(_, qtd)| qtd > 0)
+            .collect();
+        if premios.is_empty() {
+            return None;
+        }
+
+        let rateio = rateios.get(&draw.concurso);
+        let valor = premios.iter().try_fold(0u64, |total, &(faixa, qtd)| {
+            let unitario = rateio.and_then(|r| r.de(faixa)).or(padrao.de(faixa))?;
+            Some(total + unitario * qtd)
+        });
+
+        Some(Premiacao {
+            concurso: draw.clone(),
+            bolas_em_comum: aposta.bolas_em_comum(draw),
+            premios,
+            valor,
+        })
+    });
+
+    let totais = Faixa::ALL
+        .iter()
+        .map(|&faixa| {
+            let qtd = premiacoes
+                .iter()
+                .flat_map(|p| &p.premios)
+                .filter(|(f, _)| *f == faixa)
+                .map(|(_, qtd)| qtd)
+                .sum();
+            (faixa, qtd)
+        })
+        .collect();
+
+    Conferencia {
+        aposta: aposta.clone(),
+        concursos_conferidos: historico.len(),
+        total: premiacoes.iter().filter_map(|p| p.valor).sum(),
+        sem_valor: premiacoes.iter().filter(|p| p.valor.is_none()).count(),
+        totais,
+        premiacoes,
+    }
+}
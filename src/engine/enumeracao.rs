@@ -0,0 +1,320 @@
+use std::collections::BinaryHeap;
+
+use rand::Rng;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Bet, Draw, MAIOR_BOLA, combinacoes};
+use crate::engine::filters::{self, Filtros};
+use crate::engine::mega_sena_service::GeneratorConfig;
+use crate::engine::paralelo;
+use crate::error::Result;
+
+/// Quantos jogos sobraram depois de cada filtro, na ordem de aplicação.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Estagio {
+    pub filtro: &'static str,
+    pub sobreviventes: u64,
+}
+
+/// Resultado da varredura de todas as combinações.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Enumeracao {
+    /// Combinações de seis números: C(60, 6).
+    pub total: u64,
+    pub estagios: Vec<Estagio>,
+    /// Amostra uniforme dos jogos que passaram em todos os filtros.
+    pub amostra: Vec<Bet>,
+}
+
+impl Enumeracao {
+    pub fn sobreviventes(&self) -> u64 {
+        self.estagios.last().map_or(self.total, |e| e.sobreviventes)
+    }
+
+    /// Fração exata das combinações que passa em todos os filtros.
+    pub fn taxa_aceitacao(&self) -> f64 {
+        self.sobreviventes() as f64 / self.total as f64
+    }
+}
+
+/// Percorre todas as combinações permitidas pelos números fixos e excluídos,
+/// aplica os filtros de `config` contando os sobreviventes de cada etapa e
+/// sorteia até `amostra` jogos uniformemente entre os aprovados. A varredura
+/// roda em paralelo no pool do rayon.
+pub fn enumerar<R: Rng + ?Sized>(
+    historico: &[Draw],
+    config: &GeneratorConfig,
+    rng: &mut R,
+    amostra: usize,
+) -> Result<Enumeracao> {
+    let restricoes = &config.restricoes;
+    restricoes.validar()?;
+    let filtros = config.filtros(historico);
+    let sobreposicao = Sobreposicao::new(historico, filtros.qtd_toleravel);
+
+    let livres: Vec<u8> = restricoes.livres().iter().map(|b| b.value()).collect();
+    let fixos: Vec<u8> = restricoes.fixos.iter().map(|b| b.value()).collect();
+    let vagas = restricoes.vagas();
+
+    let total = combinacoes(u64::from(MAIOR_BOLA), BOLAS_POR_SORTEIO as u64);
+    let mut estagios = Vec::new();
+    if !restricoes.is_empty() {
+        estagios.push(Estagio {
+            filtro: "restricoes",
+            sobreviventes: combinacoes(livres.len() as u64, vagas as u64),
+        });
+    }
+    let nomes = [
+        filtros.soma.map(|_| "soma"),
+        filtros.paridade.map(|_| "paridade"),
+        Some("sobreposicao_historico"),
+    ];
+    let inicio_filtros = estagios.len();
+    estagios.extend(nomes.into_iter().flatten().map(|filtro| Estagio {
+        filtro,
+        sobreviventes: 0,
+    }));
+
+    // Divide a varredura pelos dois primeiros índices livres; cada prefixo
+    // é uma tarefa com seu próprio gerador, então a amostra só depende da
+    // semente tirada de `rng`.
+    let semente: u64 = rng.random();
+    let tamanho_prefixo = vagas.min(2);
+    let mut prefixos = Vec::new();
+    let mut prefixo: Vec<usize> = (0..tamanho_prefixo).collect();
+    loop {
+        prefixos.push(prefixo.clone());
+        if !proxima_combinacao(&mut prefixo, livres.len() - (vagas - tamanho_prefixo)) {
+            break;
+        }
+    }
+
+    let etapas = estagios.len() - inicio_filtros;
+    let parcial = prefixos
+        .par_iter()
+        .enumerate()
+        .map(|(tarefa, prefixo)| {
+            let mut rng = paralelo::fluxo(semente, tarefa as u64);
+            let mut parcial = Parcial::new(etapas);
+            let proximo = prefixo.last().map_or(0, |&i| i + 1);
+            let mut indices = prefixo.clone();
+            indices.extend(proximo..proximo + vagas - tamanho_prefixo);
+            let mut jogo = [0u8; BOLAS_POR_SORTEIO];
+
+            loop {
+                jogo[..fixos.len()].copy_from_slice(&fixos);
+                for (slot, &i) in jogo[fixos.len()..].iter_mut().zip(&indices) {
+                    *slot = livres[i];
+                }
+                jogo.sort_unstable();
+
+                match avaliar(&jogo, &filtros, &sobreposicao) {
+                    Some(passou) => parcial.contar(passou),
+                    None => {
+                        parcial.contar(etapas);
+                        parcial.aprovar(jogo, rng.random(), amostra);
+                    }
+                }
+
+                if !proxima_combinacao(&mut indices[tamanho_prefixo..], livres.len()) {
+                    break;
+                }
+            }
+            parcial
+        })
+        .reduce(|| Parcial::new(etapas), |a, b| a.juntar(b, amostra));
+
+    for (estagio, sobreviventes) in estagios[inicio_filtros..]
+        .iter_mut()
+        .zip(parcial.sobreviventes)
+    {
+        estagio.sobreviventes = sobreviventes;
+    }
+
+    let amostra = parcial
+        .amostra
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(_, bolas)| Bet::from_numbers(&bolas.map(i64::from)))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(Enumeracao {
+        total,
+        estagios,
+        amostra,
+    })
+}
+
+/// Contagens de uma parte da varredura.
+struct Parcial {
+    /// Sobreviventes de cada etapa de filtro.
+    sobreviventes: Vec<u64>,
+    /// Os aprovados com as menores chaves aleatórias: ficar com as `amostra`
+    /// menores chaves dá uma amostra uniforme, e juntar partes é só ficar
+    /// de novo com as menores.
+    amostra: BinaryHeap<(u64, [u8; BOLAS_POR_SORTEIO])>,
+}
+
+impl Parcial {
+    fn new(etapas: usize) -> Self {
+        Self {
+            sobreviventes: vec![0; etapas],
+            amostra: BinaryHeap::new(),
+        }
+    }
+
+    /// Conta um jogo que passou nas `etapas` primeiras etapas.
+    fn contar(&mut self, etapas: usize) {
+        for sobreviventes in &mut self.sobreviventes[..etapas] {
+            *sobreviventes += 1;
+        }
+    }
+
+    fn aprovar(&mut self, jogo: [u8; BOLAS_POR_SORTEIO], chave: u64, amostra: usize) {
+        if amostra > 0 {
+            self.amostra.push((chave, jogo));
+            if self.amostra.len() > amostra {
+                self.amostra.pop();
+            }
+        }
+    }
+
+    fn juntar(mut self, outra: Self, amostra: usize) -> Self {
+        for (a, b) in self.sobreviventes.iter_mut().zip(outra.sobreviventes) {
+            *a += b;
+        }
+        self.amostra.extend(outra.amostra);
+        while self.amostra.len() > amostra {
+            self.amostra.pop();
+        }
+        self
+    }
+}
+
+/// Devolve `None` se o jogo passa em todos os filtros, ou quantas etapas
+/// (na ordem soma, paridade, sobreposição, pulando as inativas) ele passou.
+fn avaliar(
+    jogo: &[u8; BOLAS_POR_SORTEIO],
+    filtros: &Filtros,
+    sobreposicao: &Sobreposicao,
+) -> Option<usize> {
+    let mut etapa = 0;
+
+    if let Some(intervalo) = filtros.soma {
+        let soma: u32 = jogo.iter().map(|&b| u32::from(b)).sum();
+        if filters::avaliar_soma(soma, intervalo).is_err() {
+            return Some(etapa);
+        }
+        etapa += 1;
+    }
+    if let Some(intervalo) = filtros.paridade {
+        let pares = jogo.iter().filter(|b| *b % 2 == 0).count() as u32;
+        if filters::avaliar_paridade(pares, intervalo).is_err() {
+            return Some(etapa);
+        }
+        etapa += 1;
+    }
+    if sobreposicao.bloqueia(jogo) {
+        return Some(etapa);
+    }
+
+    None
+}
+
+/// Avança `indices` (crescentes, menores que `n`) para a próxima combinação
+/// em ordem lexicográfica; devolve `false` ao passar da última.
+fn proxima_combinacao(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    let Some(i) = (0..k).rev().find(|&i| indices[i] < n - k + i) else {
+        return false;
+    };
+    indices[i] += 1;
+    for j in i + 1..k {
+        indices[j] = indices[j - 1] + 1;
+    }
+    true
+}
+
+/// Todos os subconjuntos de `k` bolas que já saíram juntos, indexados pela
+/// posição combinatória: um jogo tem `k` ou mais números em comum com algum
+/// concurso se, e só se, um de seus subconjuntos de `k` bolas estiver aqui.
+struct Sobreposicao {
+    k: usize,
+    /// Subconjuntos de `k` posições de um jogo de seis números.
+    posicoes: Vec<Vec<usize>>,
+    binomiais: [[u64; BOLAS_POR_SORTEIO + 1]; MAIOR_BOLA as usize + 1],
+    vistos: Vec<u64>,
+    historico_vazio: bool,
+}
+
+impl Sobreposicao {
+    fn new(historico: &[Draw], qtd_toleravel: u32) -> Self {
+        let k = (qtd_toleravel as usize).min(BOLAS_POR_SORTEIO + 1);
+
+        let mut binomiais = [[0u64; BOLAS_POR_SORTEIO + 1]; MAIOR_BOLA as usize + 1];
+        for (n, linha) in binomiais.iter_mut().enumerate() {
+            for (r, valor) in linha.iter_mut().enumerate() {
+                *valor = combinacoes(n as u64, r as u64);
+            }
+        }
+
+        let mut posicoes = Vec::new();
+        if (1..=BOLAS_POR_SORTEIO).contains(&k) {
+            let mut indices: Vec<usize> = (0..k).collect();
+            loop {
+                posicoes.push(indices.clone());
+                if !proxima_combinacao(&mut indices, BOLAS_POR_SORTEIO) {
+                    break;
+                }
+            }
+        }
+
+        let tamanho = if posicoes.is_empty() {
+            0
+        } else {
+            combinacoes(u64::from(MAIOR_BOLA), k as u64) as usize
+        };
+        let mut sobreposicao = Self {
+            k,
+            posicoes,
+            binomiais,
+            vistos: vec![0; tamanho.div_ceil(64)],
+            historico_vazio: historico.is_empty(),
+        };
+
+        for draw in historico {
+            let mut bolas = [0u8; BOLAS_POR_SORTEIO];
+            for (slot, b) in bolas.iter_mut().zip(draw.bolas()) {
+                *slot = b.value();
+            }
+            for p in 0..sobreposicao.posicoes.len() {
+                let posicao = sobreposicao.posicao(&bolas, p);
+                sobreposicao.vistos[posicao / 64] |= 1 << (posicao % 64);
+            }
+        }
+
+        sobreposicao
+    }
+
+    /// Posição combinatória (ordem colex) do subconjunto `p` do jogo.
+    fn posicao(&self, jogo: &[u8; BOLAS_POR_SORTEIO], p: usize) -> usize {
+        self.posicoes[p]
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| self.binomiais[usize::from(jogo[pos] - 1)][i + 1])
+            .sum::<u64>() as usize
+    }
+
+    fn bloqueia(&self, jogo: &[u8; BOLAS_POR_SORTEIO]) -> bool {
+        match self.k {
+            // Com tolerância zero, qualquer concurso já bloqueia o jogo.
+            0 => !self.historico_vazio,
+            k if k > BOLAS_POR_SORTEIO => false,
+            _ => (0..self.posicoes.len()).any(|p| {
+                let posicao = self.posicao(jogo, p);
+                self.vistos[posicao / 64] & (1 << (posicao % 64)) != 0
+            }),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Ball, Draw, MAIOR_BOLA};
+use crate::engine::filters;
+
+/// Estatísticas de um conjunto de concursos.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Estatisticas {
+    pub concursos: usize,
+    /// Quantas vezes cada bola saiu; índice 0 corresponde à bola 1.
+    pub frequencia: Vec<u32>,
+    /// Há quantos concursos cada bola não sai; índice 0 corresponde à bola 1.
+    pub atraso: Vec<u32>,
+    pub soma_minima: Option<u32>,
+    pub soma_maxima: Option<u32>,
+    /// Quantos concursos tiveram `i` bolas pares; índice `i` de 0 a 6.
+    pub pares: Vec<u32>,
+}
+
+impl Estatisticas {
+    /// Calcula as estatísticas de uma lista de concursos em ordem de concurso.
+    pub fn calcular(historico: &[Draw]) -> Self {
+        let total = MAIOR_BOLA as usize;
+        let mut frequencia = vec![0u32; total];
+        let mut atraso = vec![historico.len() as u32; total];
+        let mut pares = vec![0u32; BOLAS_POR_SORTEIO + 1];
+
+        for (i, draw) in historico.iter().enumerate() {
+            let restantes = (historico.len() - i - 1) as u32;
+            for b in draw.bolas() {
+                let idx = b.value() as usize - 1;
+                frequencia[idx] += 1;
+                atraso[idx] = restantes;
+            }
+            pares[draw.bolas().iter().filter(|b| b.value() % 2 == 0).count()] += 1;
+        }
+
+        let soma = filters::soma_min_max(historico);
+
+        Self {
+            concursos: historico.len(),
+            frequencia,
+            atraso,
+            soma_minima: soma.map(|(min, _)| min),
+            soma_maxima: soma.map(|(_, max)| max),
+            pares,
+        }
+    }
+
+    pub fn frequencia_de(&self, ball: Ball) -> u32 {
+        self.frequencia[ball.value() as usize - 1]
+    }
+
+    pub fn atraso_de(&self, ball: Ball) -> u32 {
+        self.atraso[ball.value() as usize - 1]
+    }
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use crate::core::mega_sena::{Bet, Draw};
+use crate::engine::analyser::{self, Coincidencia};
+use crate::engine::filters::{Filtros, ResultadoFiltro};
+
+/// Por que um jogo seria aceito ou bloqueado pelos filtros.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Explicacao {
+    pub aposta: Bet,
+    /// Acertos mínimos para um concurso entrar em `coincidencias`.
+    pub minimo: u32,
+    pub coincidencias: Vec<Coincidencia>,
+    pub filtros: Vec<ResultadoFiltro>,
+    pub jogavel: bool,
+}
+
+/// Lista todos os concursos com pelo menos `minimo` bolas em comum e o
+/// resultado de cada filtro, sem parar na primeira rejeição.
+pub fn explicar(aposta: &Bet, historico: &[Draw], filtros: &Filtros, minimo: u32) -> Explicacao {
+    let resultados = filtros.explicar(aposta, historico);
+    Explicacao {
+        aposta: aposta.clone(),
+        minimo,
+        coincidencias: analyser::coincidencias(aposta, historico, minimo),
+        jogavel: resultados.iter().all(|r| r.aprovado),
+        filtros: resultados,
+    }
+}
@@ -0,0 +1,205 @@
+use core::fmt;
+
+use serde::Serialize;
+
+use crate::core::mega_sena::{Bet, Draw};
+
+/// Filtros aplicados a cada jogo candidato antes de aceitá-lo no lote.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Filtros {
+    /// Rejeita o jogo se ele coincidir em pelo menos esta quantidade de bolas
+    /// com algum concurso do histórico.
+    pub qtd_toleravel: u32,
+    /// Intervalo aberto `(min, max)` aceito para a soma das bolas.
+    pub soma: Option<(u32, u32)>,
+    /// Intervalo fechado `(min, max)` aceito para a quantidade de bolas pares.
+    pub paridade: Option<(u32, u32)>,
+}
+
+/// Motivo pelo qual um jogo candidato foi rejeitado.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "filtro", rename_all = "snake_case")]
+pub enum Rejeicao {
+    SobreposicaoHistorico { concurso: Draw, acertos: u32 },
+    Soma { soma: u32, min: u32, max: u32 },
+    Paridade { pares: u32, min: u32, max: u32 },
+}
+
+/// Resultado de um filtro ao explicar um jogo.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultadoFiltro {
+    pub filtro: &'static str,
+    pub aprovado: bool,
+    pub motivo: String,
+}
+
+impl ResultadoFiltro {
+    fn new(
+        filtro: &'static str,
+        resultado: Result<(), Rejeicao>,
+        aprovado: impl FnOnce() -> String,
+    ) -> Self {
+        match resultado {
+            Ok(()) => Self {
+                filtro,
+                aprovado: true,
+                motivo: aprovado(),
+            },
+            Err(rejeicao) => Self {
+                filtro,
+                aprovado: false,
+                motivo: rejeicao.to_string(),
+            },
+        }
+    }
+}
+
+impl Rejeicao {
+    /// Nome do filtro que rejeitou, o mesmo da serialização.
+    pub fn filtro(&self) -> &'static str {
+        match self {
+            Self::SobreposicaoHistorico { .. } => "sobreposicao_historico",
+            Self::Soma { .. } => "soma",
+            Self::Paridade { .. } => "paridade",
+        }
+    }
+}
+
+impl fmt::Display for Rejeicao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SobreposicaoHistorico { concurso, acertos } => write!(
+                f,
+                "{} números coincidem com o concurso {}",
+                acertos, concurso.concurso
+            ),
+            Self::Soma { soma, min, max } => write!(
+                f,
+                "a soma {} está fora do intervalo aberto ({}, {})",
+                soma, min, max
+            ),
+            Self::Paridade { pares, min, max } => write!(
+                f,
+                "{} bolas pares, fora do intervalo [{}, {}]",
+                pares, min, max
+            ),
+        }
+    }
+}
+
+impl Filtros {
+    /// Filtros padrão: sobreposição máxima e soma dentro do intervalo
+    /// observado no histórico.
+    pub fn from_historico(historico: &[Draw], qtd_toleravel: u32) -> Self {
+        Self {
+            qtd_toleravel,
+            soma: soma_min_max(historico),
+            paridade: None,
+        }
+    }
+
+    /// Aplica os filtros e devolve o primeiro motivo de rejeição, se houver.
+    pub fn avaliar(&self, aposta: &Bet, historico: &[Draw]) -> Result<(), Rejeicao> {
+        if let Some(intervalo) = self.soma {
+            avaliar_soma(aposta.soma(), intervalo)?;
+        }
+        if let Some(intervalo) = self.paridade {
+            avaliar_paridade(pares(aposta), intervalo)?;
+        }
+        self.avaliar_sobreposicao(aposta, historico)
+    }
+
+    /// Aplica todos os filtros ativos, sem parar no primeiro, e descreve o
+    /// resultado de cada um.
+    pub fn explicar(&self, aposta: &Bet, historico: &[Draw]) -> Vec<ResultadoFiltro> {
+        let mut resultados = Vec::new();
+
+        if let Some((min, max)) = self.soma {
+            resultados.push(ResultadoFiltro::new(
+                "soma",
+                avaliar_soma(aposta.soma(), (min, max)),
+                || {
+                    format!(
+                        "a soma {} está no intervalo aberto ({}, {})",
+                        aposta.soma(),
+                        min,
+                        max
+                    )
+                },
+            ));
+        }
+        if let Some((min, max)) = self.paridade {
+            resultados.push(ResultadoFiltro::new(
+                "paridade",
+                avaliar_paridade(pares(aposta), (min, max)),
+                || {
+                    format!(
+                        "{} bolas pares, dentro do intervalo [{}, {}]",
+                        pares(aposta),
+                        min,
+                        max
+                    )
+                },
+            ));
+        }
+        resultados.push(ResultadoFiltro::new(
+            "sobreposicao_historico",
+            self.avaliar_sobreposicao(aposta, historico),
+            || {
+                format!(
+                    "nenhum concurso com {} ou mais números em comum",
+                    self.qtd_toleravel
+                )
+            },
+        ));
+
+        resultados
+    }
+
+    fn avaliar_sobreposicao(&self, aposta: &Bet, historico: &[Draw]) -> Result<(), Rejeicao> {
+        let mask = aposta.mask();
+        for draw in historico {
+            let acertos = (mask & draw.mask()).count_ones();
+            if acertos >= self.qtd_toleravel {
+                return Err(Rejeicao::SobreposicaoHistorico {
+                    concurso: draw.clone(),
+                    acertos,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn pares(aposta: &Bet) -> u32 {
+    aposta.bolas().iter().filter(|b| b.value() % 2 == 0).count() as u32
+}
+
+/// Regra do filtro de soma: `soma` precisa estar dentro do intervalo aberto.
+/// Também usada pela enumeração e pela checagem das restrições, que
+/// trabalham com a soma já calculada.
+pub(crate) fn avaliar_soma(soma: u32, (min, max): (u32, u32)) -> Result<(), Rejeicao> {
+    if soma <= min || soma >= max {
+        return Err(Rejeicao::Soma { soma, min, max });
+    }
+    Ok(())
+}
+
+/// Regra do filtro de paridade: `pares` precisa estar no intervalo fechado.
+pub(crate) fn avaliar_paridade(pares: u32, (min, max): (u32, u32)) -> Result<(), Rejeicao> {
+    if pares < min || pares > max {
+        return Err(Rejeicao::Paridade { pares, min, max });
+    }
+    Ok(())
+}
+
+/// Menor e maior soma das bolas entre os concursos da lista.
+pub fn soma_min_max(historico: &[Draw]) -> Option<(u32, u32)> {
+    historico
+        .iter()
+        .map(Draw::soma)
+        .fold(None, |acc, soma| match acc {
+            None => Some((soma, soma)),
+            Some((min, max)) => Some((min.min(soma), max.max(soma))),
+        })
+}
@@ -1,149 +1,575 @@
-use crate::core::mega_sena::MegaSena;
-use anyhow::{Context, Result};
-use chrono::Utc;
-use rand::seq::IteratorRandom;
+use core::fmt;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Ball, Bet, Draw, combinacoes};
+use crate::core::periodo::Periodo;
+use crate::engine::analyser;
+use crate::engine::estatisticas::Estatisticas;
+use crate::engine::filters::{Filtros, Rejeicao};
+use crate::engine::otimizador::Otimizacao;
+use crate::engine::paralelo::{self, TAMANHO_BLOCO};
+use crate::engine::popularidade::ModeloPopularidade;
+use crate::engine::restricoes::Restricoes;
+use crate::error::{Error, Result};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use rayon::prelude::*;
 use rusqlite::{Connection, OptionalExtension, params};
-use sha3::{Digest, Sha3_256};
-use std::collections::HashSet;
-use std::io::{BufReader, Read};
-use std::path::Path;
-use std::{fs, num};
-use uuid::{uuid, Uuid};
-
-pub fn generate_mega_sena() -> Result<MegaSena> {
-    let mut rng = rand::thread_rng();
-    let mut numbers: Vec<i64> = (1..=60)
-        .choose_multiple(&mut rng, 6)
-        .into_iter()
-        .map(|n| n as i64)
-        .collect();
-    numbers.sort_unstable();
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Como os números de cada jogo candidato são sorteados.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "nome", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Strategy {
+    /// Todas as 60 bolas com a mesma probabilidade.
+    #[default]
+    Uniforme,
+    /// Peso proporcional a quantas vezes a bola saiu nos últimos `janela`
+    /// concursos ("quentes"); com `inverso`, favorece as que menos saíram.
+    Frequencia {
+        janela: Option<usize>,
+        inverso: bool,
+    },
+    /// Peso proporcional a há quantos concursos a bola não sai, dentro dos
+    /// últimos `janela` ("atrasadas"); com `inverso`, favorece as recentes.
+    Atraso {
+        janela: Option<usize>,
+        inverso: bool,
+    },
+}
+
+impl Strategy {
+    pub fn nome(&self) -> &'static str {
+        match self {
+            Self::Uniforme => "uniforme",
+            Self::Frequencia { .. } => "frequencia",
+            Self::Atraso { .. } => "atraso",
+        }
+    }
+
+    /// Prepara o sorteio, calculando os pesos a partir do histórico (em
+    /// ordem de concurso) quando a estratégia usa pesos.
+    pub fn amostrador(&self, historico: &[Draw]) -> Amostrador {
+        let recorte = |janela: Option<usize>| {
+            let inicio = janela.map_or(0, |j| historico.len().saturating_sub(j));
+            Estatisticas::calcular(&historico[inicio..])
+        };
+
+        let pesos = match *self {
+            Self::Uniforme => None,
+            Self::Frequencia { janela, inverso } => {
+                Some(ponderar(&recorte(janela).frequencia, inverso))
+            }
+            Self::Atraso { janela, inverso } => Some(ponderar(&recorte(janela).atraso, inverso)),
+        };
+
+        Amostrador {
+            fixos: Vec::new(),
+            bolas: Ball::all().collect(),
+            pesos,
+        }
+    }
+}
+
+/// Soma 1 a cada contagem para que nenhuma bola fique com peso zero.
+fn ponderar(contagens: &[u32], inverso: bool) -> Vec<f64> {
+    contagens
+        .iter()
+        .map(|&c| {
+            let peso = f64::from(c) + 1.0;
+            if inverso { 1.0 / peso } else { peso }
+        })
+        .collect()
+}
+
+/// Sorteador de jogos de uma [`Strategy`], com os pesos já calculados.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Amostrador {
+    fixos: Vec<Ball>,
+    /// Bolas sorteáveis para completar o jogo.
+    bolas: Vec<Ball>,
+    /// Peso de cada bola; índice 0 corresponde à bola 1.
+    pesos: Option<Vec<f64>>,
+}
+
+impl Amostrador {
+    /// Coloca os fixos em todo jogo e sorteia só as vagas restantes entre as
+    /// bolas que não são fixas nem excluídas.
+    pub fn com_restricoes(mut self, restricoes: &Restricoes) -> Self {
+        self.bolas = restricoes.livres();
+        self.fixos = restricoes.fixos.clone();
+        self
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Bet> {
+        let vagas = BOLAS_POR_SORTEIO - self.fixos.len();
+        let mut bolas: Vec<Ball> = match &self.pesos {
+            None => self.bolas.choose_multiple(rng, vagas).copied().collect(),
+            Some(pesos) => self
+                .bolas
+                .choose_multiple_weighted(rng, vagas, |b| pesos[b.value() as usize - 1])
+                .map_err(|e| Error::Generation(e.to_string()))?
+                .copied()
+                .collect(),
+        };
+        bolas.extend_from_slice(&self.fixos);
+        Bet::new(bolas).map_err(|e| Error::Generation(e.to_string()))
+    }
+}
+
+/// Parâmetros de geração de um lote. Campos ausentes no JSON ficam com o
+/// valor padrão; campos desconhecidos são erro.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorConfig {
+    /// Quantidade de jogos aceitos desejada.
+    pub quantidade: usize,
+    /// Veja [`Filtros::qtd_toleravel`].
+    pub qtd_toleravel: u32,
+    pub strategy: Strategy,
+    /// Veja [`Filtros::paridade`].
+    pub paridade: Option<(u32, u32)>,
+    #[serde(default)]
+    pub restricoes: Restricoes,
+    /// Modo anti-popular: para cada jogo, sorteia esta quantidade de
+    /// candidatos aprovados e fica com o de menor popularidade.
+    #[serde(default)]
+    pub anti_popular: Option<usize>,
+    #[serde(default)]
+    pub orcamento: Orcamento,
+    /// Quando presente, o lote é melhorado por
+    /// [`otimizador::otimizar`](crate::engine::otimizador::otimizar).
+    #[serde(default)]
+    pub otimizacao: Option<Otimizacao>,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            quantidade: 10,
+            qtd_toleravel: 4,
+            strategy: Strategy::default(),
+            paridade: None,
+            restricoes: Restricoes::default(),
+            anti_popular: None,
+            orcamento: Orcamento::default(),
+            otimizacao: None,
+        }
+    }
+}
+
+impl GeneratorConfig {
+    /// Filtros desta configuração, com os limites derivados do histórico.
+    pub fn filtros(&self, historico: &[Draw]) -> Filtros {
+        Filtros {
+            paridade: self.paridade,
+            ..Filtros::from_historico(historico, self.qtd_toleravel)
+        }
+    }
+}
+
+/// Sorteia um jogo uniforme com os números fixos, completando as vagas
+/// restantes com bolas não excluídas.
+pub fn generate_mega_sena(restricoes: &Restricoes) -> Result<Bet> {
+    restricoes.validar()?;
+    Strategy::Uniforme
+        .amostrador(&[])
+        .com_restricoes(restricoes)
+        .sample(&mut rand::rng())
+}
+
+/// A cada quantas tentativas o andamento é informado.
+pub const INTERVALO_PROGRESSO: u64 = 100_000;
+
+/// Tentativas permitidas por [`Orcamento::default`].
+pub const MAX_TENTATIVAS_PADRAO: u64 = 1_000_000;
+
+/// Limites de uma geração; ao esgotá-los, a geração desiste com erro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Orcamento {
+    /// Máximo de candidatos sorteados no lote inteiro.
+    pub max_tentativas: Option<u64>,
+    pub tempo_limite: Option<Duration>,
+}
+
+impl Default for Orcamento {
+    fn default() -> Self {
+        Self {
+            max_tentativas: Some(MAX_TENTATIVAS_PADRAO),
+            tempo_limite: None,
+        }
+    }
+}
+
+/// Andamento de uma geração.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Progresso {
+    pub tentativas: u64,
+    /// Candidatos que passaram nos filtros.
+    pub aprovados: u64,
+    /// Jogos já escolhidos para o lote.
+    pub jogos: usize,
+    pub decorrido: Duration,
+}
 
-    let mut set = HashSet::new();
-    for n in &numbers {
-        set.insert(n.clone());
+impl Progresso {
+    /// Fração dos candidatos que passou nos filtros.
+    pub fn taxa_aceitacao(&self) -> f64 {
+        if self.tentativas == 0 {
+            0.0
+        } else {
+            self.aprovados as f64 / self.tentativas as f64
+        }
     }
+}
 
-    Ok(MegaSena {
-        id: 0,
-        concurso: 999999,
-        data: String::from("31/12/2025"),
-        bola_1: Option::from(numbers[0]),
-        bola_2: Option::from(numbers[1]),
-        bola_3: Option::from(numbers[2]),
-        bola_4: Option::from(numbers[3]),
-        bola_5: Option::from(numbers[4]),
-        bola_6: Option::from(numbers[5]),
-        inserted_at: String::from("Algum momento"),
-        generated_by_rust: true,
-        set: set.clone()
+impl fmt::Display for Progresso {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} tentativas, {} aprovadas ({:.4}%), {} jogos em {:.1}s",
+            self.tentativas,
+            self.aprovados,
+            100.0 * self.taxa_aceitacao(),
+            self.jogos,
+            self.decorrido.as_secs_f64()
+        )
     }
-    )
+}
 
+/// O que acontece durante [`generate_batch`].
+#[derive(Debug, Clone, Copy)]
+pub enum Evento<'a> {
+    /// Um candidato foi descartado por um filtro.
+    Rejeitado(&'a Bet, &'a Rejeicao),
+    /// Emitido a cada [`INTERVALO_PROGRESSO`] tentativas.
+    Progresso(Progresso),
 }
 
-/// Consulta um jogo gerado pelo id e imprime.
-pub fn query_generated_game(conn: &Connection, id: i64) -> Result<()> {
+/// Sorteia candidatos até obter `config.quantidade` jogos que passem nos
+/// filtros derivados do histórico, dentro de `config.orcamento`.
+/// `on_event` recebe cada rejeição e o andamento periódico.
+///
+/// Os candidatos são sorteados e avaliados em paralelo no pool do rayon; de
+/// `rng` sai só a semente dos blocos, então o mesmo `rng` semeado dá o mesmo
+/// lote com qualquer quantidade de threads.
+pub fn generate_batch<R, F>(
+    historico: &[Draw],
+    config: &GeneratorConfig,
+    rng: &mut R,
+    mut on_event: F,
+) -> Result<Vec<Bet>>
+where
+    R: Rng + ?Sized,
+    F: FnMut(Evento<'_>),
+{
+    let filtros = config.filtros(historico);
+    config.restricoes.validar_com_filtros(&filtros, historico)?;
+    let amostrador = config
+        .strategy
+        .amostrador(historico)
+        .com_restricoes(&config.restricoes);
+    let modelo = ModeloPopularidade::new(historico);
+    let candidatos = config.anti_popular.unwrap_or(1).max(1);
+    let mut jogos = Vec::with_capacity(config.quantidade);
+
+    let inicio = Instant::now();
+    let mut progresso = Progresso {
+        tentativas: 0,
+        aprovados: 0,
+        jogos: 0,
+        decorrido: Duration::ZERO,
+    };
+
+    let mut candidatos_paralelos = Candidatos {
+        historico,
+        filtros: &filtros,
+        amostrador: &amostrador,
+        semente: rng.random(),
+        proximo_bloco: 0,
+        fila: VecDeque::new(),
+    };
+    let mut proximo_aceito = |jogos: usize| -> Result<Bet> {
+        loop {
+            progresso.jogos = jogos;
+            progresso.decorrido = inicio.elapsed();
+            let esgotado = config
+                .orcamento
+                .max_tentativas
+                .is_some_and(|max| progresso.tentativas >= max)
+                || config
+                    .orcamento
+                    .tempo_limite
+                    .is_some_and(|limite| progresso.decorrido >= limite);
+            if esgotado {
+                return Err(Error::Generation(format!(
+                    "orçamento esgotado com {} de {} jogos: {}",
+                    jogos, config.quantidade, progresso
+                )));
+            }
+
+            progresso.tentativas += 1;
+            let (candidato, avaliacao) = candidatos_paralelos.proximo()?;
+            if progresso.tentativas.is_multiple_of(INTERVALO_PROGRESSO) {
+                on_event(Evento::Progresso(progresso));
+            }
+            match avaliacao {
+                Ok(()) => {
+                    progresso.aprovados += 1;
+                    return Ok(candidato);
+                }
+                Err(motivo) => on_event(Evento::Rejeitado(&candidato, &motivo)),
+            }
+        }
+    };
+
+    while jogos.len() < config.quantidade {
+        let mut escolhido = proximo_aceito(jogos.len())?;
+        if candidatos > 1 {
+            let mut menor = modelo.pontuar(&escolhido).total;
+            for _ in 1..candidatos {
+                let candidato = proximo_aceito(jogos.len())?;
+                let pontos = modelo.pontuar(&candidato).total;
+                if pontos < menor {
+                    (escolhido, menor) = (candidato, pontos);
+                }
+            }
+        }
+        jogos.push(escolhido);
+    }
+
+    Ok(jogos)
+}
+
+/// Um candidato sorteado e o resultado dos filtros para ele.
+type Avaliado = (Bet, std::result::Result<(), Rejeicao>);
+
+/// Fila de candidatos sorteados e avaliados em paralelo, em blocos de
+/// [`TAMANHO_BLOCO`] com geradores independentes, entregues na ordem dos
+/// blocos.
+struct Candidatos<'a> {
+    historico: &'a [Draw],
+    filtros: &'a Filtros,
+    amostrador: &'a Amostrador,
+    semente: u64,
+    proximo_bloco: u64,
+    fila: VecDeque<Avaliado>,
+}
+
+impl Candidatos<'_> {
+    fn proximo(&mut self) -> Result<Avaliado> {
+        if self.fila.is_empty() {
+            // Um bloco por thread a cada rodada; o que sobrar da rodada é
+            // descartado junto com a fila.
+            let blocos = rayon::current_num_threads() as u64;
+            let primeiro = self.proximo_bloco;
+            self.proximo_bloco += blocos;
+
+            let (historico, filtros, amostrador) = (self.historico, self.filtros, self.amostrador);
+            let semente = self.semente;
+            let avaliados = (primeiro..primeiro + blocos)
+                .into_par_iter()
+                .map(|bloco| {
+                    let mut rng = paralelo::fluxo(semente, bloco);
+                    (0..TAMANHO_BLOCO)
+                        .map(|_| {
+                            let candidato = amostrador.sample(&mut rng)?;
+                            let avaliacao = filtros.avaliar(&candidato, historico);
+                            Ok((candidato, avaliacao))
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            self.fila.extend(avaliados.into_iter().flatten());
+        }
+
+        Ok(self.fila.pop_front().expect("bloco de candidatos vazio"))
+    }
+}
+
+/// Estimativa, por amostragem uniforme, de quantas combinações passam nos
+/// filtros de uma configuração.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Estimativa {
+    pub amostras: u64,
+    pub aprovadas: u64,
+    /// Combinações possíveis com os números fixos e excluídos
+    /// ([`TOTAL_COMBINACOES`](crate::core::mega_sena::TOTAL_COMBINACOES) sem restrições).
+    pub espaco: u64,
+    /// Rejeições por filtro, pelo nome do filtro.
+    pub rejeicoes: BTreeMap<&'static str, u64>,
+}
+
+impl Estimativa {
+    pub fn fracao(&self) -> f64 {
+        if self.amostras == 0 {
+            0.0
+        } else {
+            self.aprovadas as f64 / self.amostras as f64
+        }
+    }
+
+    pub fn combinacoes_aprovadas(&self) -> u64 {
+        (self.fracao() * self.espaco as f64).round() as u64
+    }
+
+    /// Tentativas esperadas para aprovar `quantidade` candidatos, ou `None`
+    /// se nenhuma amostra passou.
+    pub fn tentativas_esperadas(&self, quantidade: usize) -> Option<u64> {
+        (self.aprovadas > 0).then(|| (quantidade as f64 / self.fracao()).ceil() as u64)
+    }
+
+    /// A geração provavelmente não termina dentro do orçamento.
+    pub fn inviavel(&self, config: &GeneratorConfig) -> bool {
+        let quantidade = config.quantidade * config.anti_popular.unwrap_or(1).max(1);
+        match (
+            self.tentativas_esperadas(quantidade),
+            config.orcamento.max_tentativas,
+        ) {
+            (None, _) => true,
+            (Some(esperadas), Some(max)) => esperadas > max,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Sorteia `amostras` combinações uniformes (respeitando fixos e excluídos)
+/// e conta quantas passam nos filtros de `config`, em paralelo.
+pub fn estimar_viabilidade<R: Rng + ?Sized>(
+    historico: &[Draw],
+    config: &GeneratorConfig,
+    rng: &mut R,
+    amostras: u64,
+) -> Result<Estimativa> {
+    config.restricoes.validar()?;
+    let filtros = config.filtros(historico);
+    let amostrador = Strategy::Uniforme
+        .amostrador(&[])
+        .com_restricoes(&config.restricoes);
+
+    let mut estimativa = Estimativa {
+        amostras,
+        aprovadas: 0,
+        espaco: combinacoes(
+            config.restricoes.livres().len() as u64,
+            config.restricoes.vagas() as u64,
+        ),
+        rejeicoes: BTreeMap::new(),
+    };
+    let semente: u64 = rng.random();
+    let parciais = (0..amostras.div_ceil(TAMANHO_BLOCO))
+        .into_par_iter()
+        .map(|bloco| {
+            let mut rng = paralelo::fluxo(semente, bloco);
+            let tamanho = TAMANHO_BLOCO.min(amostras - bloco * TAMANHO_BLOCO);
+            let mut aprovadas = 0;
+            let mut rejeicoes: BTreeMap<&str, u64> = BTreeMap::new();
+            for _ in 0..tamanho {
+                match filtros.avaliar(&amostrador.sample(&mut rng)?, historico) {
+                    Ok(()) => aprovadas += 1,
+                    Err(motivo) => *rejeicoes.entry(motivo.filtro()).or_default() += 1,
+                }
+            }
+            Ok((aprovadas, rejeicoes))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for (aprovadas, rejeicoes) in parciais {
+        estimativa.aprovadas += aprovadas;
+        for (filtro, n) in rejeicoes {
+            *estimativa.rejeicoes.entry(filtro).or_default() += n;
+        }
+    }
+
+    Ok(estimativa)
+}
+
+/// Um jogo gravado, com o lote de onde veio.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JogoGerado {
+    pub id: i64,
+    /// UUID do lote.
+    pub lote: String,
+    pub criado_em: String,
+    pub aposta: Bet,
+}
+
+/// Consulta um jogo gerado pelo id; `None` se não existe.
+pub fn query_generated_game(conn: &Connection, id: i64) -> Result<Option<JogoGerado>> {
     let mut stmt = conn.prepare(
-        "SELECT id, n1, n2, n3, n4, n5, n6, created_at FROM t_generated_games WHERE id = ?1",
+        "SELECT a.id, a.numeros, a.created_at, l.uuid
+         FROM t_aposta a
+         JOIN t_lote l ON l.id = a.lote_id
+         WHERE a.id = ?1",
     )?;
     let row_opt = stmt
         .query_row(params![id], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
-                row.get::<_, i64>(1)?,
-                row.get::<_, i64>(2)?,
-                row.get::<_, i64>(3)?,
-                row.get::<_, i64>(4)?,
-                row.get::<_, i64>(5)?,
-                row.get::<_, i64>(6)?,
-                row.get::<_, String>(7)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
             ))
         })
         .optional()?;
 
-    if let Some((id, n1, n2, n3, n4, n5, n6, created_at)) = row_opt {
-        println!(
-            "Jogo id={} gerado em {}: [{}, {}, {}, {}, {}, {}]",
-            id, created_at, n1, n2, n3, n4, n5, n6
-        );
-    } else {
-        println!("Nenhum jogo encontrado com id {}", id);
-    }
-
-    Ok(())
+    row_opt
+        .map(|(id, numeros, criado_em, lote)| {
+            Ok(JogoGerado {
+                id,
+                lote,
+                criado_em,
+                aposta: numeros.parse()?,
+            })
+        })
+        .transpose()
 }
 
-/// Verifica se o jogo gerado já existe no histórico.
-pub fn query_generated_game_in_history(conn: &Connection, game: &MegaSena) -> Result<()> {
-    let mut stmt = conn.prepare(
-        "SELECT concurso, data, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6
-         FROM t_mega_sena
-         WHERE bola_1 = ?1 AND bola_2 = ?2 AND bola_3 = ?3
-           AND bola_4 = ?4 AND bola_5 = ?5 AND bola_6 = ?6",
-    )?;
-
-    let row_opt = stmt
-        .query_row(
-            params![
-                game.bola_1,
-                game.bola_2,
-                game.bola_3,
-                game.bola_4,
-                game.bola_5,
-                game.bola_6
-            ],
-            |row| {
-                Ok((
-                    row.get::<_, i64>(0)?,    // concurso
-                    row.get::<_, String>(1)?, // data
-                    row.get::<_, i64>(2)?,
-                    row.get::<_, i64>(3)?,
-                    row.get::<_, i64>(4)?,
-                    row.get::<_, i64>(5)?,
-                    row.get::<_, i64>(6)?,
-                    row.get::<_, i64>(7)?,
-                ))
-            },
-        )
-        .optional()?;
-
-    if let Some((concurso, data, b1, b2, b3, b4, b5, b6)) = row_opt {
-        println!(
-            "Jogo já existente no histórico (concurso {} - {}): [{}, {}, {}, {}, {}, {}]",
-            concurso, data, b1, b2, b3, b4, b5, b6
-        );
-    } else {
-        println!("Jogo inédito! Nenhum registro encontrado no histórico.");
-    }
-
-    Ok(())
+/// Concursos do período em que o jogo gerado já saiu; vazio se é inédito.
+pub fn query_generated_game_in_history(
+    conn: &Connection,
+    game: &Bet,
+    periodo: &Periodo,
+) -> Result<Vec<Draw>> {
+    let draws = analyser::listar_historico_mega_sena(conn, periodo)?;
+    Ok(draws
+        .into_iter()
+        .filter(|d| game.acertos(d) as usize == BOLAS_POR_SORTEIO)
+        .collect())
 }
 
-pub fn save(conn: &mut Connection, mega_sena: MegaSena) -> Result<()> {
-
+/// Grava as apostas como um novo lote, junto com a estratégia e a
+/// configuração usadas, e devolve o id do lote. Com `virada`, o lote já sai
+/// marcado para a Mega da Virada daquele ano, na mesma transação.
+pub fn save(
+    conn: &mut Connection,
+    apostas: &[Bet],
+    config: &GeneratorConfig,
+    virada: Option<i32>,
+) -> Result<i64> {
+    let config_json = serde_json::to_string(config)?;
     let tx = conn.transaction()?;
 
-    let concurso = Uuid::new_v4().to_string();
-    let data = chrono::Local::now().format("%d/%m/%Y").to_string();
-    let data_typesafe = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let bola_1 = mega_sena.bola_1;
-    let bola_2 = mega_sena.bola_2;
-    let bola_3 = mega_sena.bola_3;
-    let bola_4 = mega_sena.bola_4;
-    let bola_5 = mega_sena.bola_5;
-    let bola_6 = mega_sena.bola_6;
-    let generated_by_rust = mega_sena.generated_by_rust;
-
     tx.execute(
-        "INSERT INTO t_mega_sena
-            (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6, generated_by_rust)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        "INSERT INTO t_lote (uuid, strategy, config, virada) VALUES (?1, ?2, ?3, ?4)",
         params![
-            concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6, generated_by_rust
+            Uuid::new_v4().to_string(),
+            config.strategy.nome(),
+            config_json,
+            virada
         ],
     )?;
+    let lote_id = tx.last_insert_rowid();
+
+    {
+        let mut stmt = tx.prepare("INSERT INTO t_aposta (lote_id, numeros) VALUES (?1, ?2)")?;
+        for aposta in apostas {
+            stmt.execute(params![lote_id, aposta.join(",")])?;
+        }
+    }
 
     tx.commit()?;
-    Ok(())
+    Ok(lote_id)
 }
@@ -1,2 +1,12 @@
 pub mod analyser;
+pub mod conferencia;
+pub mod enumeracao;
+pub mod estatisticas;
+pub mod explain;
+pub mod filters;
 pub mod mega_sena_service;
+pub mod otimizador;
+pub mod paralelo;
+pub mod popularidade;
+pub mod restricoes;
+pub mod virada;
@@ -0,0 +1,276 @@
+use core::fmt;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::core::mega_sena::{Ball, Bet, Draw};
+use crate::engine::mega_sena_service::{self, Evento, GeneratorConfig};
+use crate::engine::popularidade::ModeloPopularidade;
+use crate::error::{Error, Result};
+
+/// Propostas avaliadas, sem aplicar, para calibrar a temperatura inicial.
+const AQUECIMENTO: u64 = 200;
+
+/// O que a busca tenta melhorar no lote como um todo.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objetivo {
+    /// Pares distintos cobertos pelos jogos (menos sobreposição entre eles).
+    #[default]
+    CoberturaPares,
+    /// Trios distintos cobertos pelos jogos.
+    CoberturaTrios,
+    /// Todas as bolas livres aparecendo o mais perto possível da mesma
+    /// quantidade de vezes.
+    Equilibrio,
+    /// Menor popularidade média dos jogos.
+    AntiPopular,
+}
+
+impl Objetivo {
+    /// Valor do objetivo para o lote; quanto maior, melhor.
+    pub fn valor(self, jogos: &[Bet], livres: &[Ball], modelo: &ModeloPopularidade) -> f64 {
+        match self {
+            Self::CoberturaPares => cobertura(jogos, 2) as f64,
+            Self::CoberturaTrios => cobertura(jogos, 3) as f64,
+            Self::Equilibrio => -desvio_frequencias(jogos, livres),
+            Self::AntiPopular => {
+                let total: f64 = jogos.iter().map(|j| modelo.pontuar(j).total).sum();
+                -total / jogos.len().max(1) as f64
+            }
+        }
+    }
+
+    /// Descreve um valor de [`Objetivo::valor`] na unidade do objetivo.
+    pub fn descrever(self, valor: f64) -> String {
+        match self {
+            Self::CoberturaPares => format!("{} pares cobertos", valor),
+            Self::CoberturaTrios => format!("{} trios cobertos", valor),
+            Self::Equilibrio => format!("desvio de {:.3} nas frequências", -valor),
+            Self::AntiPopular => format!("popularidade média {:.2}", -valor),
+        }
+    }
+}
+
+impl fmt::Display for Objetivo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CoberturaPares => "cobertura de pares",
+            Self::CoberturaTrios => "cobertura de trios",
+            Self::Equilibrio => "equilíbrio dos números",
+            Self::AntiPopular => "anti-popularidade",
+        })
+    }
+}
+
+/// Parâmetros da busca por têmpera simulada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Otimizacao {
+    pub objetivo: Objetivo,
+    /// Propostas de troca avaliadas depois do aquecimento.
+    pub iteracoes: u64,
+    pub tempo_limite: Option<Duration>,
+    /// Lotes aleatórios gerados para comparar com o resultado.
+    pub lotes_aleatorios: usize,
+}
+
+impl Default for Otimizacao {
+    fn default() -> Self {
+        Self {
+            objetivo: Objetivo::default(),
+            iteracoes: 20_000,
+            tempo_limite: None,
+            lotes_aleatorios: 10,
+        }
+    }
+}
+
+/// Lote encontrado pela busca e a comparação com lotes aleatórios.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Otimizado {
+    pub jogos: Vec<Bet>,
+    pub objetivo: Objetivo,
+    pub valor: f64,
+    /// Valor do lote aleatório de onde a busca partiu.
+    pub inicial: f64,
+    /// Média do objetivo em [`Otimizacao::lotes_aleatorios`] lotes gerados
+    /// sem otimização, ou `None` se nenhum foi gerado.
+    pub aleatorio: Option<f64>,
+    pub iteracoes: u64,
+    /// Trocas aceitas pela busca.
+    pub aceitas: u64,
+}
+
+/// Gera um lote com [`mega_sena_service::generate_batch`] e o melhora por
+/// têmpera simulada segundo `config.otimizacao` (ou o padrão): a cada passo
+/// troca uma bola de um jogo ou um jogo inteiro, mantendo só lotes sem
+/// jogos repetidos e com todos os jogos aprovados pelos filtros.
+pub fn otimizar<R, F>(
+    historico: &[Draw],
+    config: &GeneratorConfig,
+    rng: &mut R,
+    mut on_event: F,
+) -> Result<Otimizado>
+where
+    R: Rng + ?Sized,
+    F: FnMut(Evento<'_>),
+{
+    let otimizacao = config.otimizacao.unwrap_or_default();
+    let objetivo = otimizacao.objetivo;
+    let mut jogos = mega_sena_service::generate_batch(historico, config, rng, &mut on_event)?;
+    if jogos.is_empty() {
+        return Err(Error::Generation("lote vazio para otimizar".to_string()));
+    }
+
+    let filtros = config.filtros(historico);
+    let amostrador = config
+        .strategy
+        .amostrador(historico)
+        .com_restricoes(&config.restricoes);
+    let modelo = ModeloPopularidade::new(historico);
+    let livres = config.restricoes.livres();
+    let valor = |jogos: &[Bet]| objetivo.valor(jogos, &livres, &modelo);
+
+    let inicial = valor(&jogos);
+    let mut atual = inicial;
+    let mut melhor = (inicial, jogos.clone());
+
+    let propor = |jogos: &[Bet], rng: &mut R| -> Result<Option<(usize, Bet)>> {
+        let i = rng.random_range(0..jogos.len());
+        let novo = if rng.random_bool(0.5) {
+            amostrador.sample(rng)?
+        } else {
+            trocar_bola(&jogos[i], &livres, rng)?
+        };
+        let valido = !jogos.contains(&novo) && filtros.avaliar(&novo, historico).is_ok();
+        Ok(valido.then_some((i, novo)))
+    };
+
+    let mut pioras = Vec::new();
+    for _ in 0..AQUECIMENTO {
+        if let Some((i, novo)) = propor(&jogos, rng)? {
+            let anterior = std::mem::replace(&mut jogos[i], novo);
+            let delta = valor(&jogos) - atual;
+            jogos[i] = anterior;
+            if delta < 0.0 {
+                pioras.push(-delta);
+            }
+        }
+    }
+    let temperatura_inicial = if pioras.is_empty() {
+        1.0
+    } else {
+        pioras.iter().sum::<f64>() / pioras.len() as f64
+    };
+
+    let inicio = Instant::now();
+    let mut iteracoes = 0;
+    let mut aceitas = 0;
+    while iteracoes < otimizacao.iteracoes
+        && otimizacao
+            .tempo_limite
+            .is_none_or(|limite| inicio.elapsed() < limite)
+    {
+        let fracao = iteracoes as f64 / otimizacao.iteracoes as f64;
+        let temperatura = temperatura_inicial * (1.0 - fracao);
+        iteracoes += 1;
+
+        let Some((i, novo)) = propor(&jogos, rng)? else {
+            continue;
+        };
+        let anterior = std::mem::replace(&mut jogos[i], novo);
+        let proposto = valor(&jogos);
+        let delta = proposto - atual;
+        if delta >= 0.0 || (temperatura > 0.0 && rng.random::<f64>() < (delta / temperatura).exp())
+        {
+            atual = proposto;
+            aceitas += 1;
+            if atual > melhor.0 {
+                melhor = (atual, jogos.clone());
+            }
+        } else {
+            jogos[i] = anterior;
+        }
+    }
+
+    let mut aleatorios = Vec::with_capacity(otimizacao.lotes_aleatorios);
+    for _ in 0..otimizacao.lotes_aleatorios {
+        let lote = mega_sena_service::generate_batch(historico, config, rng, |_| {})?;
+        aleatorios.push(valor(&lote));
+    }
+
+    let (valor, jogos) = melhor;
+    Ok(Otimizado {
+        jogos,
+        objetivo,
+        valor,
+        inicial,
+        aleatorio: (!aleatorios.is_empty())
+            .then(|| aleatorios.iter().sum::<f64>() / aleatorios.len() as f64),
+        iteracoes,
+        aceitas,
+    })
+}
+
+/// Troca uma bola do jogo que não seja fixa por uma livre fora do jogo.
+fn trocar_bola<R: Rng + ?Sized>(jogo: &Bet, livres: &[Ball], rng: &mut R) -> Result<Bet> {
+    let trocaveis: Vec<Ball> = jogo
+        .bolas()
+        .iter()
+        .copied()
+        .filter(|b| livres.contains(b))
+        .collect();
+    let fora: Vec<Ball> = livres
+        .iter()
+        .copied()
+        .filter(|b| !jogo.contains(*b))
+        .collect();
+    let (Some(&sai), Some(&entra)) = (trocaveis.choose(rng), fora.choose(rng)) else {
+        return Ok(jogo.clone());
+    };
+
+    let bolas = jogo
+        .bolas()
+        .iter()
+        .map(|&b| if b == sai { entra } else { b })
+        .collect();
+    Bet::new(bolas).map_err(|e| Error::Generation(e.to_string()))
+}
+
+/// Subconjuntos distintos de `k` bolas presentes em algum jogo.
+fn cobertura(jogos: &[Bet], k: usize) -> usize {
+    let mut vistos = HashSet::new();
+    for jogo in jogos {
+        subconjuntos(jogo.bolas(), k, 0, 0, &mut vistos);
+    }
+    vistos.len()
+}
+
+fn subconjuntos(bolas: &[Ball], k: usize, inicio: usize, mask: u64, vistos: &mut HashSet<u64>) {
+    if k == 0 {
+        vistos.insert(mask);
+        return;
+    }
+    for i in inicio..=bolas.len().saturating_sub(k) {
+        subconjuntos(bolas, k - 1, i + 1, mask | 1 << bolas[i].value(), vistos);
+    }
+}
+
+/// Desvio padrão de quantas vezes cada bola livre aparece no lote.
+fn desvio_frequencias(jogos: &[Bet], livres: &[Ball]) -> f64 {
+    if livres.is_empty() {
+        return 0.0;
+    }
+    let contagens: Vec<f64> = livres
+        .iter()
+        .map(|&b| jogos.iter().filter(|j| j.contains(b)).count() as f64)
+        .collect();
+    let media = contagens.iter().sum::<f64>() / contagens.len() as f64;
+    let variancia =
+        contagens.iter().map(|c| (c - media).powi(2)).sum::<f64>() / contagens.len() as f64;
+    variancia.sqrt()
+}
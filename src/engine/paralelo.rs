@@ -0,0 +1,36 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+
+use crate::core::mega_sena::Draw;
+
+/// Candidatos sorteados por tarefa paralela na geração e na estimativa.
+pub const TAMANHO_BLOCO: u64 = 1024;
+
+/// Concursos comparados por tarefa paralela nas varreduras do histórico.
+pub const CONCURSOS_POR_BLOCO: usize = 256;
+
+/// Gerador independente para a tarefa `indice` de uma execução com
+/// `semente`. Cada tarefa tem sua própria sequência, então o resultado só
+/// depende da semente e não da quantidade de threads nem da ordem em que
+/// as tarefas rodam.
+pub fn fluxo(semente: u64, indice: u64) -> StdRng {
+    let mut chave = [0u8; 32];
+    chave[..8].copy_from_slice(&semente.to_le_bytes());
+    chave[8..16].copy_from_slice(&indice.to_le_bytes());
+    StdRng::from_seed(chave)
+}
+
+/// Aplica `f` a cada concurso do histórico no pool do rayon, em blocos de
+/// [`CONCURSOS_POR_BLOCO`], e devolve os resultados presentes na ordem do
+/// histórico, como faria a varredura sequencial.
+pub fn varrer<T, F>(historico: &[Draw], f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&Draw) -> Option<T> + Sync,
+{
+    historico
+        .par_chunks(CONCURSOS_POR_BLOCO)
+        .flat_map_iter(|bloco| bloco.iter().filter_map(&f))
+        .collect()
+}
@@ -0,0 +1,186 @@
+use core::fmt;
+
+use serde::Serialize;
+
+use crate::core::mega_sena::{Ball, Bet, Draw};
+
+/// Concursos recentes considerados pelo componente [`Componente::Recentes`].
+pub const JANELA_RECENTES: usize = 10;
+
+/// Maior número que pode ser dia do mês.
+const MAIOR_DIA: u8 = 31;
+
+/// Colunas do volante: 6 linhas de 10 números (01–10, 11–20, …).
+const COLUNAS_VOLANTE: u8 = 10;
+
+/// Vieses de escolha que fazem muita gente marcar o mesmo jogo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Componente {
+    /// Datas de aniversário: excesso de números até 31.
+    Calendario,
+    /// Progressões aritméticas, como 05-10-15-20 ou números seguidos.
+    Sequencias,
+    /// Números alinhados numa linha, coluna ou diagonal do volante.
+    Volante,
+    /// Repetição de números dos últimos concursos.
+    Recentes,
+}
+
+impl fmt::Display for Componente {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Calendario => "calendário",
+            Self::Sequencias => "sequências",
+            Self::Volante => "volante",
+            Self::Recentes => "recentes",
+        })
+    }
+}
+
+/// Escore de popularidade de um jogo: quanto maior, mais gente
+/// provavelmente marcou o mesmo jogo e menor a fatia de cada ganhador.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Popularidade {
+    pub total: f64,
+    pub componentes: Vec<(Componente, f64)>,
+}
+
+impl fmt::Display for Popularidade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}", self.total)?;
+        let relevantes: Vec<String> = self
+            .componentes
+            .iter()
+            .filter(|(_, pontos)| *pontos > 0.0)
+            .map(|(c, pontos)| format!("{} {:.1}", c, pontos))
+            .collect();
+        if !relevantes.is_empty() {
+            write!(f, " ({})", relevantes.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Modelo heurístico de popularidade. Cada componente vale de 0 a 30
+/// pontos e é zero para um jogo sem o viés correspondente.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeloPopularidade {
+    recentes: Vec<Draw>,
+}
+
+impl ModeloPopularidade {
+    /// `historico` em ordem de concurso; só os últimos concursos importam.
+    pub fn new(historico: &[Draw]) -> Self {
+        let inicio = historico.len().saturating_sub(JANELA_RECENTES);
+        Self {
+            recentes: historico[inicio..].to_vec(),
+        }
+    }
+
+    pub fn pontuar(&self, aposta: &Bet) -> Popularidade {
+        let componentes = vec![
+            (Componente::Calendario, calendario(aposta)),
+            (Componente::Sequencias, sequencias(aposta)),
+            (Componente::Volante, volante(aposta)),
+            (Componente::Recentes, self.recentes(aposta)),
+        ];
+        Popularidade {
+            total: componentes.iter().map(|(_, pontos)| pontos).sum(),
+            componentes,
+        }
+    }
+
+    /// 6 pontos por número repetido do concurso recente mais parecido,
+    /// a partir do segundo; repetir um resultado inteiro vale 30.
+    fn recentes(&self, aposta: &Bet) -> f64 {
+        let repetidos = self
+            .recentes
+            .iter()
+            .map(|d| aposta.acertos(d))
+            .max()
+            .unwrap_or(0);
+        (6.0 * (f64::from(repetidos) - 1.0)).clamp(0.0, 30.0)
+    }
+}
+
+/// Metade dos números até 31 é o esperado; acima disso, até 30 pontos
+/// quando todos poderiam ser datas.
+fn calendario(aposta: &Bet) -> f64 {
+    let datas = aposta
+        .bolas()
+        .iter()
+        .filter(|b| b.value() <= MAIOR_DIA)
+        .count() as f64;
+    let fracao = datas / aposta.len() as f64;
+    (60.0 * (fracao - 0.5)).max(0.0)
+}
+
+/// 3 pontos por par de números seguidos e 8 por termo além do terceiro na
+/// maior progressão aritmética.
+fn sequencias(aposta: &Bet) -> f64 {
+    let seguidos = aposta
+        .bolas()
+        .windows(2)
+        .filter(|par| par[1].value() == par[0].value() + 1)
+        .count() as f64;
+    let termos = maior_progressao(aposta.bolas()) as f64;
+    (3.0 * seguidos + 8.0 * (termos - 3.0).max(0.0)).min(30.0)
+}
+
+/// 6 pontos por número além do segundo na linha do volante (horizontal,
+/// vertical ou diagonal) com mais números marcados.
+fn volante(aposta: &Bet) -> f64 {
+    let posicoes: Vec<(i32, i32)> = aposta
+        .bolas()
+        .iter()
+        .map(|b| {
+            let i = i32::from(b.value() - 1);
+            (
+                i / i32::from(COLUNAS_VOLANTE),
+                i % i32::from(COLUNAS_VOLANTE),
+            )
+        })
+        .collect();
+
+    let retas: [fn((i32, i32)) -> i32; 4] = [
+        |(linha, _)| linha,
+        |(_, coluna)| coluna,
+        |(linha, coluna)| linha - coluna,
+        |(linha, coluna)| linha + coluna,
+    ];
+    let alinhados = retas
+        .iter()
+        .map(|reta| {
+            posicoes
+                .iter()
+                .map(|&p| posicoes.iter().filter(|&&q| reta(q) == reta(p)).count())
+                .max()
+                .unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0);
+
+    (6.0 * (alinhados as f64 - 2.0)).clamp(0.0, 30.0)
+}
+
+/// Quantidade de termos da maior progressão aritmética entre as bolas.
+fn maior_progressao(bolas: &[Ball]) -> usize {
+    let valores: Vec<i32> = bolas.iter().map(|b| i32::from(b.value())).collect();
+    let mut maior = valores.len().min(2);
+
+    for i in 0..valores.len() {
+        for j in i + 1..valores.len() {
+            let razao = valores[j] - valores[i];
+            let mut termos = 2;
+            let mut proximo = valores[j] + razao;
+            while valores.binary_search(&proximo).is_ok() {
+                termos += 1;
+                proximo += razao;
+            }
+            maior = maior.max(termos);
+        }
+    }
+
+    maior
+}
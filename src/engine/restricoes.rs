@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Ball, Draw, MAIOR_BOLA};
+use crate::engine::filters::{self, Filtros};
+use crate::error::{Error, Result};
+
+/// Números que entram em todo jogo do lote e números que nunca entram.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Restricoes {
+    pub fixos: Vec<Ball>,
+    pub excluidos: Vec<Ball>,
+}
+
+impl Restricoes {
+    pub fn is_empty(&self) -> bool {
+        self.fixos.is_empty() && self.excluidos.is_empty()
+    }
+
+    /// Bolas disponíveis para completar os jogos.
+    pub fn livres(&self) -> Vec<Ball> {
+        Ball::all()
+            .filter(|b| !self.fixos.contains(b) && !self.excluidos.contains(b))
+            .collect()
+    }
+
+    /// Quantas bolas cada jogo ainda sorteia além das fixas.
+    pub fn vagas(&self) -> usize {
+        BOLAS_POR_SORTEIO.saturating_sub(self.fixos.len())
+    }
+
+    /// Confere se os conjuntos são coerentes entre si.
+    pub fn validar(&self) -> Result<()> {
+        if self.fixos.len() > BOLAS_POR_SORTEIO {
+            return Err(Error::Config(format!(
+                "no máximo {} números fixos; informados {}",
+                BOLAS_POR_SORTEIO,
+                self.fixos.len()
+            )));
+        }
+        for (nome, bolas) in [("fixos", &self.fixos), ("excluídos", &self.excluidos)] {
+            if let Some(b) = bolas
+                .iter()
+                .enumerate()
+                .find_map(|(i, b)| bolas[..i].contains(b).then_some(b))
+            {
+                return Err(Error::Config(format!("número {} repetido nos {}", b, nome)));
+            }
+        }
+        if let Some(b) = self.fixos.iter().find(|b| self.excluidos.contains(b)) {
+            return Err(Error::Config(format!(
+                "o número {} está entre os fixos e os excluídos",
+                b
+            )));
+        }
+        let livres = self.livres().len();
+        if livres < self.vagas() {
+            return Err(Error::Config(format!(
+                "sobram {} números para {} vagas em cada jogo",
+                livres,
+                self.vagas()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Além de [`Restricoes::validar`], confere se algum jogo com estas
+    /// restrições pode passar nos filtros: soma e paridade são verificadas
+    /// exatamente; a sobreposição com o histórico, apenas pelos fixos.
+    pub fn validar_com_filtros(&self, filtros: &Filtros, historico: &[Draw]) -> Result<()> {
+        self.validar()?;
+
+        let fixos_mask = self.fixos.iter().fold(0u64, |m, b| m | 1 << b.value());
+        if let Some(draw) = historico
+            .iter()
+            .find(|d| (d.mask() & fixos_mask).count_ones() >= filtros.qtd_toleravel)
+        {
+            return Err(Error::Generation(format!(
+                "os números fixos já somam {} acertos com o concurso {}; \
+                 todo jogo seria bloqueado pelo histórico",
+                (draw.mask() & fixos_mask).count_ones(),
+                draw.concurso
+            )));
+        }
+
+        if filtros.soma.is_none() && filtros.paridade.is_none() {
+            return Ok(());
+        }
+
+        let soma_fixos: u32 = self.fixos.iter().map(|b| u32::from(b.value())).sum();
+        let pares_fixos = self.fixos.iter().filter(|b| b.value() % 2 == 0).count() as u32;
+        let aceita = |soma: u32, pares: u32| {
+            filtros
+                .soma
+                .is_none_or(|intervalo| filters::avaliar_soma(soma, intervalo).is_ok())
+                && filtros
+                    .paridade
+                    .is_none_or(|intervalo| filters::avaliar_paridade(pares, intervalo).is_ok())
+        };
+
+        let possivel = combinacoes_possiveis(&self.livres(), self.vagas())
+            .into_iter()
+            .any(|(soma, pares)| aceita(soma_fixos + soma, pares_fixos + pares));
+        if !possivel {
+            return Err(Error::Generation(
+                "nenhum jogo com estes números fixos e excluídos passa nos filtros de soma e paridade"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Pares (soma, quantidade de pares) alcançáveis escolhendo `k` bolas de `bolas`.
+fn combinacoes_possiveis(bolas: &[Ball], k: usize) -> Vec<(u32, u32)> {
+    let soma_maxima = MAIOR_BOLA as usize * BOLAS_POR_SORTEIO;
+    // alcancavel[c][p][s]: dá para escolher c bolas, p delas pares, somando s.
+    let mut alcancavel = vec![vec![vec![false; soma_maxima + 1]; k + 1]; k + 1];
+    alcancavel[0][0][0] = true;
+
+    for bola in bolas {
+        let valor = bola.value() as usize;
+        let par = usize::from(valor.is_multiple_of(2));
+        for c in (0..k).rev() {
+            for p in 0..=c {
+                for s in 0..=soma_maxima - valor {
+                    if alcancavel[c][p][s] {
+                        alcancavel[c + 1][p + par][s + valor] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut possiveis = Vec::new();
+    for (p, somas) in alcancavel[k].iter().enumerate() {
+        for (s, &ok) in somas.iter().enumerate() {
+            if ok {
+                possiveis.push((s as u32, p as u32));
+            }
+        }
+    }
+    possiveis
+}
@@ -0,0 +1,163 @@
+//! Mega da Virada: o concurso especial de 31 de dezembro, que não acumula e
+//! sorteia a reserva formada ao longo do ano pelos concursos regulares.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::core::mega_sena::Draw;
+use crate::core::periodo::Periodo;
+use crate::engine::analyser;
+use crate::engine::estatisticas::Estatisticas;
+use crate::error::Result;
+use crate::shared::date;
+
+/// Ano do primeiro sorteio especial da Mega da Virada.
+pub const PRIMEIRO_ANO: i32 = 2008;
+
+/// Concursos da Mega da Virada já realizados, por ano. Nos anos fora da
+/// lista vale a regra da data: o concurso de 31 de dezembro.
+pub const CONCURSOS: &[(i32, u32)] = &[
+    (2008, 1035),
+    (2009, 1140),
+    (2010, 1245),
+    (2011, 1350),
+    (2012, 1455),
+    (2013, 1560),
+    (2014, 1665),
+    (2015, 1775),
+    (2016, 1890),
+    (2017, 2000),
+    (2018, 2110),
+    (2019, 2220),
+    (2020, 2330),
+    (2021, 2440),
+    (2022, 2550),
+    (2023, 2670),
+    (2024, 2810),
+];
+
+/// Data da Mega da Virada de `ano`.
+pub fn data(ano: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, 12, 31).expect("31 de dezembro existe em todo ano")
+}
+
+/// Se o concurso foi uma Mega da Virada.
+pub fn e_virada(draw: &Draw) -> bool {
+    let ano = draw.data.year();
+    match CONCURSOS.iter().find(|(a, _)| *a == ano) {
+        Some(&(_, concurso)) => draw.concurso == concurso,
+        None => ano >= PRIMEIRO_ANO && draw.data == data(ano),
+    }
+}
+
+/// Quanto falta para a próxima Mega da Virada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Contagem {
+    pub ano: i32,
+    pub data: NaiveDate,
+    /// Dias a partir de hoje; 0 no próprio dia 31.
+    pub dias: i64,
+}
+
+impl Contagem {
+    /// A Virada do ano de `hoje`, que é sempre a próxima (ou a de hoje).
+    pub fn a_partir_de(hoje: NaiveDate) -> Self {
+        let ano = hoje.year();
+        let data = data(ano);
+        Self {
+            ano,
+            data,
+            dias: (data - hoje).num_days(),
+        }
+    }
+}
+
+impl fmt::Display for Contagem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Mega da Virada {} ({}): ",
+            self.ano,
+            date::format_data_br(self.data)
+        )?;
+        match self.dias {
+            0 => f.write_str("é hoje!"),
+            1 => f.write_str("é amanhã"),
+            dias => write!(f, "faltam {} dias", dias),
+        }
+    }
+}
+
+/// Uma Mega da Virada do histórico.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Virada {
+    pub concurso: Draw,
+    /// Prêmio pago a cada ganhador da sena, em centavos, quando importado.
+    pub rateio_sena: Option<u64>,
+}
+
+/// Valor acumulado informado pela Caixa em um concurso, em centavos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Acumulado {
+    pub concurso: u32,
+    pub centavos: u64,
+}
+
+/// Tudo o que o comando `virada` mostra.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PanoramaVirada {
+    pub proxima: Contagem,
+    /// Viradas do histórico, em ordem de concurso.
+    pub viradas: Vec<Virada>,
+    /// Estatísticas só das Viradas; o atraso é contado em Viradas.
+    pub estatisticas: Estatisticas,
+    /// Reserva para a próxima Virada, informada no concurso mais recente
+    /// que a traz depois da última Virada.
+    pub reserva: Option<Acumulado>,
+    /// Prêmio principal acumulado no concurso mais recente que o informa.
+    pub acumulado_sena: Option<Acumulado>,
+}
+
+/// Monta o panorama a partir do histórico completo, em ordem de concurso.
+pub fn panorama(conn: &Connection, historico: &[Draw], hoje: NaiveDate) -> Result<PanoramaVirada> {
+    let rateios = analyser::listar_rateios(conn, &Periodo::todos())?;
+    let concursos: Vec<Draw> = historico.iter().filter(|d| e_virada(d)).cloned().collect();
+    let estatisticas = Estatisticas::calcular(&concursos);
+    let ultima = concursos.last().map_or(0, |d| d.concurso);
+    let viradas = concursos
+        .into_iter()
+        .map(|concurso| Virada {
+            rateio_sena: rateios.get(&concurso.concurso).and_then(|r| r.sena),
+            concurso,
+        })
+        .collect();
+
+    Ok(PanoramaVirada {
+        proxima: Contagem::a_partir_de(hoje),
+        viradas,
+        estatisticas,
+        reserva: ultimo_acumulado(conn, "acumulado_virada_centavos")?
+            .filter(|a| a.concurso > ultima),
+        acumulado_sena: ultimo_acumulado(conn, "acumulado_sena_centavos")?,
+    })
+}
+
+fn ultimo_acumulado(conn: &Connection, coluna: &'static str) -> Result<Option<Acumulado>> {
+    let sql = format!(
+        "SELECT concurso, {coluna} FROM t_mega_sena
+         WHERE {coluna} IS NOT NULL
+         ORDER BY concurso DESC LIMIT 1"
+    );
+    let acumulado = conn
+        .query_row(&sql, [], |row| {
+            Ok(Acumulado {
+                concurso: row.get(0)?,
+                centavos: row.get(1)?,
+            })
+        })
+        .optional()?;
+    Ok(acumulado)
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use crate::core::mega_sena::InvalidGame;
+
+/// Erros do gerador. Cada variante corresponde a um código de saída distinto
+/// no binário (ver [`Error::exit_code`]).
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("erro no banco de dados: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("erro na migration {arquivo}: {motivo}")]
+    Migration { arquivo: String, motivo: String },
+
+    #[error("erro ao importar {origem}: {motivo}")]
+    Import { origem: String, motivo: String },
+
+    #[error("jogo inválido: {0}")]
+    Validation(#[from] InvalidGame),
+
+    #[error("erro na geração de jogos: {0}")]
+    Generation(String),
+
+    #[error("erro de E/S: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("configuração inválida: {0}")]
+    Config(String),
+
+    #[error("erro de JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn migration(arquivo: impl Into<String>, motivo: impl fmt::Display) -> Self {
+        Self::Migration {
+            arquivo: arquivo.into(),
+            motivo: motivo.to_string(),
+        }
+    }
+
+    pub fn import(origem: impl Into<String>, motivo: impl fmt::Display) -> Self {
+        Self::Import {
+            origem: origem.into(),
+            motivo: motivo.to_string(),
+        }
+    }
+
+    /// Código de saída do processo para este erro. O 1 fica reservado para
+    /// falhas genéricas e o 2 para erros de argumentos (clap).
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Database(_) => 10,
+            Self::Migration { .. } => 11,
+            Self::Import { .. } => 12,
+            Self::Validation(_) => 13,
+            Self::Generation(_) => 14,
+            Self::Io(_) => 15,
+            Self::Config(_) => 16,
+            Self::Json(_) => 17,
+        }
+    }
+}
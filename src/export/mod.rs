@@ -0,0 +1,114 @@
+//! Exportação de lotes gravados para arquivos e mensagens.
+
+pub mod relatorio;
+pub mod volante;
+
+use std::io::Write;
+
+use serde_json::Value;
+
+use crate::database::lotes::Lote;
+use crate::error::Result;
+
+/// Formatos de [`exportar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formato {
+    /// Uma linha por aposta, com os dados do lote repetidos.
+    Csv,
+    /// Os lotes com as apostas aninhadas.
+    Json,
+    /// Uma tabela por lote, para colar no chat.
+    Markdown,
+}
+
+/// Escreve os lotes em `saida` no formato pedido.
+pub fn exportar<W: Write>(lotes: &[Lote], formato: Formato, mut saida: W) -> Result<()> {
+    match formato {
+        Formato::Csv => exportar_csv(lotes, saida),
+        Formato::Json => {
+            serde_json::to_writer_pretty(&mut saida, lotes)?;
+            writeln!(saida)?;
+            Ok(())
+        }
+        Formato::Markdown => exportar_markdown(lotes, saida),
+    }
+}
+
+fn exportar_csv<W: Write>(lotes: &[Lote], saida: W) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(saida);
+    csv.write_record([
+        "lote_id",
+        "lote_uuid",
+        "criado_em",
+        "strategy",
+        "config",
+        "aposta_id",
+        "numeros",
+    ])
+    .map_err(std::io::Error::from)?;
+
+    for lote in lotes {
+        let config = lote
+            .config
+            .as_ref()
+            .map(Value::to_string)
+            .unwrap_or_default();
+        for aposta in &lote.apostas {
+            csv.write_record([
+                lote.id.to_string().as_str(),
+                &lote.uuid,
+                lote.criado_em.as_deref().unwrap_or(""),
+                lote.strategy.as_deref().unwrap_or(""),
+                &config,
+                &aposta.id.to_string(),
+                &aposta.numeros.join(" "),
+            ])
+            .map_err(std::io::Error::from)?;
+        }
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+fn exportar_markdown<W: Write>(lotes: &[Lote], mut saida: W) -> Result<()> {
+    for (i, lote) in lotes.iter().enumerate() {
+        if i > 0 {
+            writeln!(saida)?;
+        }
+        write!(saida, "### Lote {}", lote.id)?;
+        if let Some(criado_em) = &lote.criado_em {
+            write!(saida, " · {}", criado_em)?;
+        }
+        writeln!(saida, "\n")?;
+
+        if let Some(strategy) = &lote.strategy {
+            writeln!(saida, "Estratégia: `{}`  ", strategy)?;
+        }
+        if let Some(Value::Object(config)) = &lote.config {
+            let parametros: Vec<String> = config
+                .iter()
+                .filter(|(_, valor)| !valor.is_null())
+                .map(|(chave, valor)| format!("`{}: {}`", chave, valor))
+                .collect();
+            writeln!(saida, "Parâmetros: {}", parametros.join(" · "))?;
+        }
+        writeln!(saida)?;
+
+        writeln!(saida, "| # | Números |")?;
+        writeln!(saida, "|---:|---|")?;
+        for (n, aposta) in lote.apostas.iter().enumerate() {
+            writeln!(saida, "| {} | {} |", n + 1, aposta.numeros.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Escapa texto para SVG e HTML.
+fn escapar_xml(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
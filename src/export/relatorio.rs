@@ -0,0 +1,403 @@
+//! Relatório de estatísticas em HTML para apresentar ao bolão: um arquivo
+//! só, com os gráficos em SVG embutido e sem nenhum recurso externo.
+
+use std::fmt::Write as _;
+
+use chrono::NaiveDate;
+
+use super::escapar_xml;
+use crate::core::mega_sena::{
+    BOLAS_POR_SORTEIO, Bet, Draw, MAIOR_BOLA, TOTAL_COMBINACOES, combinacoes,
+};
+use crate::core::periodo::Periodo;
+use crate::database::lotes::Lote;
+use crate::engine::estatisticas::Estatisticas;
+use crate::shared::date;
+
+/// Quantidade de concursos na tabela de últimos resultados.
+const ULTIMOS: usize = 10;
+/// Largura das faixas do histograma de somas.
+const FAIXA_SOMA: u32 = 15;
+const MENOR_SOMA: u32 = 21;
+const MAIOR_SOMA: u32 = 345;
+
+const LARGURA: f64 = 800.0;
+const ALTURA: f64 = 240.0;
+const MARGEM_ESQUERDA: f64 = 44.0;
+const MARGEM_DIREITA: f64 = 8.0;
+const MARGEM_SUPERIOR: f64 = 10.0;
+const MARGEM_INFERIOR: f64 = 28.0;
+
+const ESTILO: &str = "\
+body { font-family: Helvetica, Arial, sans-serif; max-width: 860px; margin: 2em auto; padding: 0 1em; color: #222; }
+header { border-bottom: 4px solid #209869; margin-bottom: 1.5em; }
+h1 { color: #209869; margin-bottom: 0.2em; }
+h2 { margin-top: 1.8em; }
+.nota { color: #666; font-size: 0.9em; }
+svg { width: 100%; height: auto; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.numero { text-align: right; }
+.bola { display: inline-block; width: 1.9em; line-height: 1.9em; margin: 0 0.1em; border-radius: 50%; background: #209869; color: #fff; text-align: center; font-weight: bold; font-size: 0.85em; }
+footer { margin-top: 3em; color: #999; font-size: 0.8em; }
+";
+
+/// Identificação do relatório.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cabecalho {
+    pub titulo: String,
+    /// Período de onde veio o histórico.
+    pub periodo: Periodo,
+    /// O histórico traz só os concursos da Mega da Virada.
+    pub so_viradas: bool,
+    pub gerado_em: NaiveDate,
+}
+
+/// Linha de referência de um gráfico de barras.
+enum Referencia {
+    Nenhuma,
+    /// Uma linha horizontal neste valor.
+    Constante(f64),
+    /// Um valor por barra, ligados por uma linha.
+    PorBarra(Vec<f64>),
+}
+
+/// Relatório completo, com o histórico em ordem de concurso e,
+/// opcionalmente, o lote que o bolão vai jogar.
+pub fn html(historico: &[Draw], lote: Option<&Lote>, cabecalho: &Cabecalho) -> String {
+    let stats = Estatisticas::calcular(historico);
+    let concursos = stats.concursos as f64;
+    let mut html = String::new();
+
+    let titulo = escapar_xml(&cabecalho.titulo);
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"pt-BR\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{titulo}</title>\n<style>\n{ESTILO}</style>\n</head>\n<body>"
+    );
+    let _ = writeln!(
+        html,
+        "<header>\n<h1>{titulo}</h1>\n<p>{} concursos{} · {} · gerado em {}</p>\n</header>",
+        stats.concursos,
+        if cabecalho.so_viradas {
+            " da Mega da Virada"
+        } else {
+            ""
+        },
+        descrever_periodo(&cabecalho.periodo),
+        date::format_data_br(cabecalho.gerado_em)
+    );
+
+    let rotulos_bolas: Vec<String> = (1..=MAIOR_BOLA).map(|b| format!("{:02}", b)).collect();
+    let media = concursos * BOLAS_POR_SORTEIO as f64 / MAIOR_BOLA as f64;
+    secao(
+        &mut html,
+        "Frequência",
+        &format!(
+            "Quantas vezes cada bola saiu. A linha marca a média esperada, {:.1} vezes por bola.",
+            media
+        ),
+        &barras(
+            &rotulos_bolas,
+            &valores(&stats.frequencia),
+            &Referencia::Constante(media),
+            5,
+        ),
+    );
+    secao(
+        &mut html,
+        "Atraso",
+        "Há quantos concursos cada bola não sai.",
+        &barras(
+            &rotulos_bolas,
+            &valores(&stats.atraso),
+            &Referencia::Nenhuma,
+            5,
+        ),
+    );
+
+    let (rotulos_somas, somas, somas_esperadas) = histograma_somas(historico);
+    secao(
+        &mut html,
+        "Soma das bolas",
+        "Concursos por faixa de soma das seis bolas. A linha mostra quantos seriam \
+         esperados se todos os jogos tivessem a mesma chance.",
+        &barras(
+            &rotulos_somas,
+            &somas,
+            &Referencia::PorBarra(somas_esperadas),
+            2,
+        ),
+    );
+
+    let rotulos_pares: Vec<String> = (0..=BOLAS_POR_SORTEIO)
+        .map(|pares| format!("{}P/{}I", pares, BOLAS_POR_SORTEIO - pares))
+        .collect();
+    let pares_esperados = (0..=BOLAS_POR_SORTEIO as u64)
+        .map(|pares| {
+            let impares = BOLAS_POR_SORTEIO as u64 - pares;
+            concursos * (combinacoes(30, pares) * combinacoes(30, impares)) as f64
+                / TOTAL_COMBINACOES as f64
+        })
+        .collect();
+    secao(
+        &mut html,
+        "Pares e ímpares",
+        "Concursos por quantidade de bolas pares, com a linha do esperado.",
+        &barras(
+            &rotulos_pares,
+            &valores(&stats.pares),
+            &Referencia::PorBarra(pares_esperados),
+            1,
+        ),
+    );
+
+    html.push_str("<section>\n<h2>Últimos resultados</h2>\n");
+    if historico.is_empty() {
+        html.push_str("<p>Nenhum concurso no período.</p>\n");
+    } else {
+        html.push_str(
+            "<table>\n<tr><th>Concurso</th><th>Data</th><th>Números</th><th>Soma</th></tr>\n",
+        );
+        for draw in historico.iter().rev().take(ULTIMOS) {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"numero\">{}</td></tr>",
+                draw.concurso,
+                date::format_data_br(draw.data),
+                bolas(draw.bolas().iter().map(|b| b.to_string())),
+                draw.soma()
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+
+    html.push_str("<section>\n<h2>Nosso lote</h2>\n");
+    match lote {
+        Some(lote) => escrever_lote(&mut html, lote, historico),
+        None => html.push_str("<p>Nenhum lote gravado.</p>\n"),
+    }
+    html.push_str("</section>\n");
+
+    let _ = writeln!(
+        html,
+        "<footer>Gerado por mega-da-virada-generator {}.</footer>\n</body>\n</html>",
+        env!("CARGO_PKG_VERSION")
+    );
+    html
+}
+
+fn escrever_lote(html: &mut String, lote: &Lote, historico: &[Draw]) {
+    let _ = write!(html, "<p>Lote {}", lote.id);
+    if let Some(criado_em) = &lote.criado_em {
+        let _ = write!(html, " · criado em {}", escapar_xml(criado_em));
+    }
+    if let Some(strategy) = &lote.strategy {
+        let _ = write!(html, " · estratégia {}", escapar_xml(strategy));
+    }
+    html.push_str("</p>\n");
+
+    html.push_str(
+        "<table>\n<tr><th>#</th><th>Números</th><th>Soma</th><th>Pares</th>\
+         <th>Maior acerto no histórico</th></tr>\n",
+    );
+    for (i, aposta) in lote.apostas.iter().enumerate() {
+        let jogo = &aposta.numeros;
+        let maior_acerto = historico
+            .iter()
+            .map(|draw| jogo.acertos(draw))
+            .max()
+            .unwrap_or(0);
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"numero\">{}</td>\
+             <td class=\"numero\">{}</td><td class=\"numero\">{}</td></tr>",
+            i + 1,
+            bolas(jogo.bolas().iter().map(|b| b.to_string())),
+            jogo.soma(),
+            pares(jogo),
+            maior_acerto
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn secao(html: &mut String, titulo: &str, nota: &str, grafico: &str) {
+    let _ = writeln!(
+        html,
+        "<section>\n<h2>{}</h2>\n<p class=\"nota\">{}</p>\n{}</section>",
+        titulo, nota, grafico
+    );
+}
+
+fn bolas(numeros: impl Iterator<Item = String>) -> String {
+    numeros
+        .map(|n| format!("<span class=\"bola\">{}</span>", n))
+        .collect()
+}
+
+fn pares(jogo: &Bet) -> usize {
+    jogo.bolas()
+        .iter()
+        .filter(|b| b.value().is_multiple_of(2))
+        .count()
+}
+
+fn valores(contagens: &[u32]) -> Vec<f64> {
+    contagens.iter().map(|&c| c as f64).collect()
+}
+
+fn descrever_periodo(periodo: &Periodo) -> String {
+    match (periodo.de, periodo.ate) {
+        (None, None) => "todo o histórico".to_string(),
+        (Some(de), None) => format!("a partir de {}", date::format_data_br(de)),
+        (None, Some(ate)) => format!("até {}", date::format_data_br(ate)),
+        (Some(de), Some(ate)) => format!(
+            "de {} a {}",
+            date::format_data_br(de),
+            date::format_data_br(ate)
+        ),
+    }
+}
+
+/// Rótulos, concursos observados e esperados por faixa de soma.
+fn histograma_somas(historico: &[Draw]) -> (Vec<String>, Vec<f64>, Vec<f64>) {
+    let faixas = ((MAIOR_SOMA - MENOR_SOMA) / FAIXA_SOMA + 1) as usize;
+    let faixa = |soma: u32| ((soma - MENOR_SOMA) / FAIXA_SOMA) as usize;
+
+    let rotulos = (0..faixas)
+        .map(|i| {
+            let inicio = MENOR_SOMA + i as u32 * FAIXA_SOMA;
+            format!("{}–{}", inicio, (inicio + FAIXA_SOMA - 1).min(MAIOR_SOMA))
+        })
+        .collect();
+
+    let mut observados = vec![0.0; faixas];
+    for draw in historico {
+        observados[faixa(draw.soma())] += 1.0;
+    }
+
+    let mut esperados = vec![0.0; faixas];
+    let escala = historico.len() as f64 / TOTAL_COMBINACOES as f64;
+    for (soma, jogos) in jogos_por_soma().into_iter().enumerate() {
+        if jogos > 0 {
+            esperados[faixa(soma as u32)] += jogos as f64 * escala;
+        }
+    }
+
+    (rotulos, observados, esperados)
+}
+
+/// Quantos jogos de seis números têm cada soma (índice = soma).
+fn jogos_por_soma() -> Vec<u64> {
+    let maior = MAIOR_SOMA as usize;
+    // jogos[k][s]: subconjuntos de k bolas com soma s entre as já vistas.
+    let mut jogos = vec![vec![0u64; maior + 1]; BOLAS_POR_SORTEIO + 1];
+    jogos[0][0] = 1;
+    for bola in 1..=MAIOR_BOLA as usize {
+        for k in (1..=BOLAS_POR_SORTEIO).rev() {
+            for soma in (bola..=maior).rev() {
+                jogos[k][soma] += jogos[k - 1][soma - bola];
+            }
+        }
+    }
+    jogos.swap_remove(BOLAS_POR_SORTEIO)
+}
+
+/// Gráfico de barras em SVG, com o rótulo de uma barra a cada
+/// `rotulo_a_cada`, a partir da primeira.
+fn barras(
+    rotulos: &[String],
+    valores: &[f64],
+    referencia: &Referencia,
+    rotulo_a_cada: usize,
+) -> String {
+    let largura_util = LARGURA - MARGEM_ESQUERDA - MARGEM_DIREITA;
+    let altura_util = ALTURA - MARGEM_SUPERIOR - MARGEM_INFERIOR;
+    let base = MARGEM_SUPERIOR + altura_util;
+
+    let maior_referencia = match referencia {
+        Referencia::Nenhuma => 0.0,
+        Referencia::Constante(valor) => *valor,
+        Referencia::PorBarra(valores) => valores.iter().copied().fold(0.0, f64::max),
+    };
+    let maximo = valores
+        .iter()
+        .copied()
+        .fold(maior_referencia, f64::max)
+        .max(1.0)
+        * 1.05;
+    let y = |valor: f64| base - valor / maximo * altura_util;
+    let passo = largura_util / valores.len().max(1) as f64;
+    let centro = |i: usize| MARGEM_ESQUERDA + passo * (i as f64 + 0.5);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {LARGURA} {ALTURA}" font-family="Helvetica, Arial, sans-serif" font-size="10">"#
+    );
+    for fracao in [0.0, 0.5, 1.0] {
+        let valor = maximo / 1.05 * fracao;
+        let _ = writeln!(
+            svg,
+            "  <line x1=\"{MARGEM_ESQUERDA}\" y1=\"{0:.1}\" x2=\"{1}\" y2=\"{0:.1}\" stroke=\"#ddd\"/>\n  \
+             <text x=\"{2}\" y=\"{3:.1}\" text-anchor=\"end\" fill=\"#666\">{4:.0}</text>",
+            y(valor),
+            LARGURA - MARGEM_DIREITA,
+            MARGEM_ESQUERDA - 4.0,
+            y(valor) + 3.0,
+            valor
+        );
+    }
+
+    for (i, (rotulo, &valor)) in rotulos.iter().zip(valores).enumerate() {
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#209869\"><title>{}: {}</title></rect>",
+            centro(i) - passo * 0.4,
+            y(valor),
+            passo * 0.8,
+            base - y(valor),
+            escapar_xml(rotulo),
+            valor
+        );
+        if i % rotulo_a_cada == 0 {
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#444\">{}</text>",
+                centro(i),
+                base + 14.0,
+                escapar_xml(rotulo)
+            );
+        }
+    }
+
+    match referencia {
+        Referencia::Nenhuma => {}
+        Referencia::Constante(valor) => {
+            let _ = writeln!(
+                svg,
+                "  <line x1=\"{MARGEM_ESQUERDA}\" y1=\"{0:.1}\" x2=\"{1}\" y2=\"{0:.1}\" stroke=\"#e8a33c\" stroke-width=\"2\" stroke-dasharray=\"6 4\"/>",
+                y(*valor),
+                LARGURA - MARGEM_DIREITA
+            );
+        }
+        Referencia::PorBarra(esperados) => {
+            let pontos: Vec<String> = esperados
+                .iter()
+                .enumerate()
+                .map(|(i, &valor)| format!("{:.1},{:.1}", centro(i), y(valor)))
+                .collect();
+            let _ = writeln!(
+                svg,
+                "  <polyline points=\"{}\" fill=\"none\" stroke=\"#e8a33c\" stroke-width=\"2\"/>",
+                pontos.join(" ")
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,430 @@
+//! Réplica do volante da Mega-Sena (grade 6×10) para conferir ou copiar
+//! os jogos, em SVG (um por jogo) ou PDF (uma página por jogo).
+//!
+//! O desenho é montado uma vez em milímetros, com a origem no canto
+//! superior esquerdo do volante, e depois traduzido para cada formato.
+
+use std::fmt::Write as _;
+
+use super::escapar_xml;
+use crate::core::mega_sena::{BOLAS_POR_SORTEIO, Ball, Bet, MAIOR_BOLA, MAX_BOLAS_APOSTA};
+
+/// Largura e altura do volante, em mm.
+pub const LARGURA: f64 = 100.0;
+pub const ALTURA: f64 = 120.0;
+
+/// Página A4 do PDF, em mm.
+const LARGURA_A4: f64 = 210.0;
+const ALTURA_A4: f64 = 297.0;
+const MARGEM_SUPERIOR: f64 = 20.0;
+
+const COLUNAS: u8 = 10;
+const CELULA: f64 = 8.0;
+const PASSO: f64 = 9.0;
+const INICIO_GRADE: f64 = 30.0;
+const INICIO_QUANTIDADE: f64 = 93.0;
+
+type Cor = (u8, u8, u8);
+const VERDE: Cor = (0x20, 0x98, 0x69);
+const PRETO: Cor = (0, 0, 0);
+const BRANCO: Cor = (0xff, 0xff, 0xff);
+
+/// Identificação impressa no topo de cada volante.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cabecalho {
+    /// Nome do bolão.
+    pub bolao: Option<String>,
+    pub lote: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Elemento {
+    Retangulo {
+        x: f64,
+        y: f64,
+        largura: f64,
+        altura: f64,
+        preenchimento: Option<Cor>,
+        contorno: Option<Cor>,
+    },
+    /// `y` é a linha de base do texto.
+    Texto {
+        x: f64,
+        y: f64,
+        tamanho: f64,
+        cor: Cor,
+        negrito: bool,
+        centralizado: bool,
+        conteudo: String,
+    },
+}
+
+impl Elemento {
+    fn texto(x: f64, y: f64, tamanho: f64, conteudo: impl Into<String>) -> Self {
+        Self::Texto {
+            x,
+            y,
+            tamanho,
+            cor: PRETO,
+            negrito: false,
+            centralizado: false,
+            conteudo: conteudo.into(),
+        }
+    }
+}
+
+/// Desenha o volante do jogo `indice` (a partir de 1) de um lote de `total`.
+fn desenhar(jogo: &Bet, indice: usize, total: usize, cabecalho: &Cabecalho) -> Vec<Elemento> {
+    let mut elementos = vec![
+        Elemento::Retangulo {
+            x: 0.0,
+            y: 0.0,
+            largura: LARGURA,
+            altura: ALTURA,
+            preenchimento: Some(BRANCO),
+            contorno: Some(VERDE),
+        },
+        Elemento::Retangulo {
+            x: 0.0,
+            y: 0.0,
+            largura: LARGURA,
+            altura: 14.0,
+            preenchimento: Some(VERDE),
+            contorno: None,
+        },
+        Elemento::Texto {
+            x: 5.0,
+            y: 9.5,
+            tamanho: 6.0,
+            cor: BRANCO,
+            negrito: true,
+            centralizado: false,
+            conteudo: "MEGA-SENA".to_string(),
+        },
+    ];
+
+    let bolao = cabecalho.bolao.as_deref().unwrap_or("—");
+    elementos.push(Elemento::texto(5.0, 20.0, 3.5, format!("Bolão: {}", bolao)));
+    let mut identificacao = String::new();
+    if let Some(lote) = cabecalho.lote {
+        let _ = write!(identificacao, "Lote {} · ", lote);
+    }
+    let _ = write!(identificacao, "Jogo {} de {}", indice, total);
+    elementos.push(Elemento::texto(5.0, 25.5, 3.0, identificacao));
+
+    for numero in 1..=MAIOR_BOLA {
+        let (linha, coluna) = ((numero - 1) / COLUNAS, (numero - 1) % COLUNAS);
+        let (x, y) = (
+            5.0 + f64::from(coluna) * PASSO,
+            INICIO_GRADE + f64::from(linha) * PASSO,
+        );
+        let marcado = Ball::new(numero).is_ok_and(|b| jogo.contains(b));
+        let caixa = (x, y, CELULA, CELULA);
+        quadro(
+            &mut elementos,
+            caixa,
+            marcado,
+            format!("{:02}", numero),
+            3.5,
+        );
+    }
+
+    elementos.push(Elemento::texto(
+        5.0,
+        INICIO_QUANTIDADE - 2.0,
+        3.0,
+        "Quantidade de números",
+    ));
+    for (i, quantidade) in (BOLAS_POR_SORTEIO..=MAX_BOLAS_APOSTA).enumerate() {
+        let x = 5.0 + i as f64 * 6.0;
+        let marcado = quantidade == jogo.len();
+        let caixa = (x, INICIO_QUANTIDADE, 5.4, 6.0);
+        quadro(&mut elementos, caixa, marcado, quantidade.to_string(), 2.6);
+    }
+
+    let numeros: Vec<String> = jogo.bolas().iter().map(|b| b.to_string()).collect();
+    for (i, linha) in numeros.chunks(10).enumerate() {
+        let rotulo = if i == 0 { "Números: " } else { "" };
+        elementos.push(Elemento::texto(
+            5.0,
+            106.0 + i as f64 * 4.5,
+            3.2,
+            format!("{}{}", rotulo, linha.join(" ")),
+        ));
+    }
+    elementos.push(Elemento::texto(
+        5.0,
+        117.0,
+        2.4,
+        "Confira as marcações antes de registrar a aposta.",
+    ));
+
+    elementos
+}
+
+/// Um quadro numerado do volante em `(x, y, largura, altura)`, preenchido
+/// de preto quando marcado.
+fn quadro(
+    elementos: &mut Vec<Elemento>,
+    (x, y, largura, altura): (f64, f64, f64, f64),
+    marcado: bool,
+    conteudo: String,
+    tamanho: f64,
+) {
+    elementos.push(Elemento::Retangulo {
+        x,
+        y,
+        largura,
+        altura,
+        preenchimento: marcado.then_some(PRETO),
+        contorno: Some(VERDE),
+    });
+    elementos.push(Elemento::Texto {
+        x: x + largura / 2.0,
+        y: y + altura / 2.0 + tamanho * 0.35,
+        tamanho,
+        cor: if marcado { BRANCO } else { VERDE },
+        negrito: marcado,
+        centralizado: true,
+        conteudo,
+    });
+}
+
+/// Volante do jogo `indice` (a partir de 1) de um lote de `total`, em SVG.
+pub fn svg(jogo: &Bet, indice: usize, total: usize, cabecalho: &Cabecalho) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{LARGURA}mm" height="{ALTURA}mm" viewBox="0 0 {LARGURA} {ALTURA}" font-family="Helvetica, Arial, sans-serif">"#
+    );
+    for elemento in desenhar(jogo, indice, total, cabecalho) {
+        match elemento {
+            Elemento::Retangulo {
+                x,
+                y,
+                largura,
+                altura,
+                preenchimento,
+                contorno,
+            } => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <rect x="{x:.2}" y="{y:.2}" width="{largura:.2}" height="{altura:.2}" fill="{}" stroke="{}" stroke-width="0.3"/>"#,
+                    cor_svg(preenchimento),
+                    cor_svg(contorno)
+                );
+            }
+            Elemento::Texto {
+                x,
+                y,
+                tamanho,
+                cor,
+                negrito,
+                centralizado,
+                conteudo,
+            } => {
+                let _ = writeln!(
+                    svg,
+                    r#"  <text x="{x:.2}" y="{y:.2}" font-size="{tamanho:.2}" fill="{}"{}{}>{}</text>"#,
+                    cor_svg(Some(cor)),
+                    if negrito {
+                        r#" font-weight="bold""#
+                    } else {
+                        ""
+                    },
+                    if centralizado {
+                        r#" text-anchor="middle""#
+                    } else {
+                        ""
+                    },
+                    escapar_xml(&conteudo)
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn cor_svg(cor: Option<Cor>) -> String {
+    match cor {
+        Some((r, g, b)) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        None => "none".to_string(),
+    }
+}
+
+/// Todos os jogos num PDF A4, um volante por página, com as fontes padrão
+/// do PDF (Helvetica) para não depender de arquivos de fonte.
+pub fn pdf(jogos: &[Bet], cabecalho: &Cabecalho) -> Vec<u8> {
+    let mut objetos: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        Vec::new(), // Páginas, preenchido depois de conhecer os ids.
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    let mut paginas = Vec::with_capacity(jogos.len());
+    for (i, jogo) in jogos.iter().enumerate() {
+        let conteudo = conteudo_pdf(&desenhar(jogo, i + 1, jogos.len(), cabecalho));
+        let mut stream = format!("<< /Length {} >>\nstream\n", conteudo.len()).into_bytes();
+        stream.extend_from_slice(&conteudo);
+        stream.extend_from_slice(b"\nendstream");
+        objetos.push(stream);
+        let id_conteudo = objetos.len();
+
+        objetos.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                pontos(LARGURA_A4),
+                pontos(ALTURA_A4),
+                id_conteudo
+            )
+            .into_bytes(),
+        );
+        paginas.push(format!("{} 0 R", objetos.len()));
+    }
+    objetos[1] = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        paginas.join(" "),
+        paginas.len()
+    )
+    .into_bytes();
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut deslocamentos = Vec::with_capacity(objetos.len());
+    for (i, objeto) in objetos.iter().enumerate() {
+        deslocamentos.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(objeto);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let inicio_xref = pdf.len();
+    let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objetos.len() + 1);
+    for deslocamento in deslocamentos {
+        let _ = writeln!(xref, "{:010} 00000 n ", deslocamento);
+    }
+    let _ = write!(
+        xref,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objetos.len() + 1,
+        inicio_xref
+    );
+    pdf.extend_from_slice(xref.as_bytes());
+    pdf
+}
+
+fn pontos(mm: f64) -> f64 {
+    mm * 72.0 / 25.4
+}
+
+/// Operadores de desenho da página, com o volante centralizado no topo.
+fn conteudo_pdf(elementos: &[Elemento]) -> Vec<u8> {
+    let origem_x = (LARGURA_A4 - LARGURA) / 2.0;
+    let x_pdf = |x: f64| pontos(origem_x + x);
+    let y_pdf = |y: f64| pontos(ALTURA_A4 - MARGEM_SUPERIOR - y);
+    let cor = |(r, g, b): Cor| {
+        format!(
+            "{:.3} {:.3} {:.3}",
+            f64::from(r) / 255.0,
+            f64::from(g) / 255.0,
+            f64::from(b) / 255.0
+        )
+    };
+
+    let mut conteudo = Vec::new();
+    conteudo.extend_from_slice(format!("{:.2} w\n", pontos(0.3)).as_bytes());
+    for elemento in elementos {
+        match elemento {
+            Elemento::Retangulo {
+                x,
+                y,
+                largura,
+                altura,
+                preenchimento,
+                contorno,
+            } => {
+                let operador = match (preenchimento, contorno) {
+                    (Some(_), Some(_)) => "B",
+                    (Some(_), None) => "f",
+                    (None, Some(_)) => "S",
+                    (None, None) => continue,
+                };
+                if let Some(c) = preenchimento {
+                    conteudo.extend_from_slice(format!("{} rg\n", cor(*c)).as_bytes());
+                }
+                if let Some(c) = contorno {
+                    conteudo.extend_from_slice(format!("{} RG\n", cor(*c)).as_bytes());
+                }
+                conteudo.extend_from_slice(
+                    format!(
+                        "{:.2} {:.2} {:.2} {:.2} re {}\n",
+                        x_pdf(*x),
+                        y_pdf(y + altura),
+                        pontos(*largura),
+                        pontos(*altura),
+                        operador
+                    )
+                    .as_bytes(),
+                );
+            }
+            Elemento::Texto {
+                x,
+                y,
+                tamanho,
+                cor: c,
+                negrito,
+                centralizado,
+                conteudo: texto,
+            } => {
+                let tamanho = pontos(*tamanho);
+                let mut x = x_pdf(*x);
+                if *centralizado {
+                    x -= largura_helvetica(texto) * tamanho / 2.0;
+                }
+                let fonte = if *negrito { "F2" } else { "F1" };
+                conteudo.extend_from_slice(
+                    format!(
+                        "BT /{} {:.2} Tf {} rg {:.2} {:.2} Td (",
+                        fonte,
+                        tamanho,
+                        cor(*c),
+                        x,
+                        y_pdf(*y)
+                    )
+                    .as_bytes(),
+                );
+                conteudo.extend(texto_pdf(texto));
+                conteudo.extend_from_slice(b") Tj ET\n");
+            }
+        }
+    }
+    conteudo
+}
+
+/// Largura aproximada do texto em Helvetica, em unidades do tamanho da
+/// fonte. Os algarismos, que são o que se centraliza, têm largura exata.
+fn largura_helvetica(texto: &str) -> f64 {
+    texto
+        .chars()
+        .map(|c| if c == ' ' { 0.278 } else { 0.556 })
+        .sum()
+}
+
+/// Texto em WinAnsi (Latin-1 cobre os acentos do português), com os
+/// caracteres especiais das strings do PDF escapados.
+fn texto_pdf(texto: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(texto.len());
+    for c in texto.chars() {
+        match c {
+            '(' | ')' | '\\' => bytes.extend([b'\\', c as u8]),
+            '—' => bytes.push(0x97),
+            c if u32::from(c) <= 0xff => bytes.push(c as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
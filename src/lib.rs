@@ -0,0 +1,34 @@
+//! Gerador de jogos da Mega-Sena filtrados pelo histórico de concursos.
+//!
+//! O ponto de entrada é [`Store`], que abre o banco SQLite já migrado e expõe
+//! importação, geração, conferência de apostas e estatísticas.
+
+pub mod api;
+pub mod core;
+pub mod database;
+pub mod engine;
+pub mod error;
+pub mod export;
+pub mod shared;
+pub mod store;
+pub mod tui;
+
+pub use crate::core::mega_sena::{Ball, Bet, Draw, InvalidGame};
+pub use crate::core::periodo::Periodo;
+pub use crate::database::lotes::{Lote, SelecaoLotes};
+pub use crate::database::mega_sena_bootstrap::Ingestao;
+pub use crate::database::migrations::DriftMode;
+pub use crate::database::mission_control::Flag;
+pub use crate::engine::analyser::Coincidencia;
+pub use crate::engine::conferencia::{Conferencia, Faixa, Rateio};
+pub use crate::engine::enumeracao::Enumeracao;
+pub use crate::engine::estatisticas::Estatisticas;
+pub use crate::engine::explain::Explicacao;
+pub use crate::engine::filters::{Filtros, Rejeicao, ResultadoFiltro};
+pub use crate::engine::mega_sena_service::{Evento, GeneratorConfig, Orcamento, Strategy};
+pub use crate::engine::otimizador::{Objetivo, Otimizacao, Otimizado};
+pub use crate::engine::popularidade::{ModeloPopularidade, Popularidade};
+pub use crate::engine::restricoes::Restricoes;
+pub use crate::engine::virada::PanoramaVirada;
+pub use crate::error::{Error, Result};
+pub use crate::store::{Store, StoreOptions};
@@ -1,137 +1,149 @@
-use anyhow::{Context, Result};
-use chrono::Utc;
-use rand::seq::IteratorRandom;
-use rusqlite::{Connection, OptionalExtension, params};
-use std::io::{BufReader, Read};
-use std::path::Path;
-use std::{fs, num};
-
-pub mod core;
-pub mod database;
-pub mod engine;
-pub mod shared;
-
-use database::{csv, migrations, mega_sena_bootstrap};
-use engine::mega_sena_service;
-use shared::sha3;
-
-use crate::core::mega_sena::MegaSena;
-use crate::engine::analyser;
-
-const QTD_TOLERAVEL: u8 = 4;
-const PRINT_NAO_JOGAVEL: bool = false;
-const QTD_JOGOS_DESEJADOS: u8 = 10;
-
-fn main() -> Result<()> {
-    let db_path = "mega_sena.db";
-    let csv_mega_sena_path = "mega_sena.csv";
-
-    let mut conn = Connection::open(db_path)?;
-    println!("Conectado ao SQLite em {}", db_path);
-
-    if !migrations::check_migration_table_exists(&conn)? {
-        println!("Tabela t_migration não existe. Criando...");
-        migrations::create_migration_table(&conn)?;
-    }
-
-    database::migrations::run_migrations(&conn)?;
-
-
-    mega_sena_bootstrap::bootstrap_mega_sena_data_from_csv();
-
-    let historico_mega_sela_list = match analyser::listar_historico_mega_sena(&conn) {
-        Ok(r) => r,
-        Err(_) => panic!("❌ Erro ao carregar histórico da Mega-Sena"),
-    };
-
-    let mut jogos_jogaveis_desejados: u8 = QTD_JOGOS_DESEJADOS;
-    let mut jogos_gerados: Vec<MegaSena> = Vec::with_capacity(jogos_jogaveis_desejados as usize);
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use mega_da_virada_generator::database::snapshot::SnapshotPolicy;
+use mega_da_virada_generator::{DriftMode, Error, Result, StoreOptions};
+
+mod commands;
+
+use commands::Contexto;
+use commands::backup::{BackupArgs, RestoreArgs};
+use commands::check::CheckArgs;
+use commands::enumerate::EnumerateArgs;
+use commands::explain::ExplainArgs;
+use commands::export::ExportArgs;
+use commands::flags::FlagsCommand;
+use commands::generate::GenerateArgs;
+use commands::migrate::MigrateCommand;
+use commands::relatorio::RelatorioArgs;
+use commands::serve::ServeArgs;
+use commands::virada::ViradaArgs;
+use commands::volante::VolanteArgs;
+
+/// Gerador de jogos da Mega-Sena filtrados pelo histórico de concursos.
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+struct Args {
+    /// Caminho do banco SQLite.
+    #[arg(long, global = true, default_value = "mega_sena.db")]
+    db: PathBuf,
+
+    /// Falha se alguma migration aplicada tiver checksum divergente (padrão).
+    #[arg(long, global = true, conflicts_with = "lenient")]
+    strict: bool,
+
+    /// Apenas avisa sobre checksums divergentes e segue aplicando migrations.
+    #[arg(long, global = true)]
+    lenient: bool,
+
+    /// Quantidade de snapshots mantidos em `snapshots/`, ao lado do banco.
+    #[arg(long, global = true, default_value_t = NonZeroUsize::new(5).unwrap())]
+    keep_snapshots: NonZeroUsize,
+
+    /// Não grava snapshots automáticos antes de migrations, rollback e restore.
+    #[arg(long, global = true)]
+    no_snapshot: bool,
+
+    /// Threads usadas na geração e na varredura (padrão: um por núcleo).
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Sem subcomando, gera um lote com estes argumentos.
+    #[command(flatten)]
+    generate: GenerateArgs,
+}
 
-    let mut soma_minima = 346;
-    let mut soma_maxima = 0;
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Gera e grava um lote de jogos (comando padrão).
+    Generate(GenerateArgs),
+    /// Confere um jogo contra todo o histórico: acertos, quinas, quadras e prêmios.
+    Check(CheckArgs),
+    /// Percorre as 50.063.860 combinações aplicando os filtros e conta os aprovados.
+    Enumerate(EnumerateArgs),
+    /// Explica por que um jogo seria aceito ou bloqueado pelos filtros.
+    Explain(ExplainArgs),
+    /// Exporta lotes gravados em CSV, JSON ou Markdown.
+    Export(ExportArgs),
+    /// Desenha os jogos de um lote em volantes para imprimir (PDF ou SVG).
+    Volante(VolanteArgs),
+    /// Gera um relatório HTML com as estatísticas e o lote que será jogado.
+    Relatorio(RelatorioArgs),
+    /// Sobe a API HTTP local (JSON) sobre o mesmo banco.
+    Serve(ServeArgs),
+    /// Mega da Virada: contagem regressiva, Viradas do histórico e suas estatísticas.
+    Virada(ViradaArgs),
+    /// Interface interativa no terminal: histórico, estatísticas e gerador.
+    Tui,
+    /// Gerencia as migrations do banco.
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommand,
+    },
+    /// Grava uma cópia do banco (backup online do SQLite).
+    Backup(BackupArgs),
+    /// Substitui o banco pelo conteúdo de um backup ou snapshot.
+    Restore(RestoreArgs),
+    /// Consulta e altera as feature flags gravadas em `t_mission_control`.
+    Flags {
+        #[command(subcommand)]
+        command: FlagsCommand,
+    },
+}
 
-    for j in &historico_mega_sela_list {
-        if j.generated_by_rust{
-            continue;
-        }
-        let soma = j.bola_1.unwrap_or(0) +
-            j.bola_2.unwrap_or(0) +
-            j.bola_3.unwrap_or(0) +
-            j.bola_4.unwrap_or(0) +
-            j.bola_5.unwrap_or(0) +
-            j.bola_6.unwrap_or(0);
-        if soma < soma_minima {
-            soma_minima = soma;
-        }
+fn main() -> ExitCode {
+    let args = Args::parse();
 
-        if soma > soma_maxima {
-            soma_maxima = soma;
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ExitCode::from(e.exit_code())
         }
     }
+}
 
-    println!("\n\n\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Soma minima {}", soma_minima);
-    println!("Soma maxima {}", soma_maxima);
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-
-
-    while jogos_jogaveis_desejados > 0 {
-        let generated_mega_sena: MegaSena = engine::mega_sena_service::generate_mega_sena()?;
-
-        let mut ocorrencias_encontradas = false;
-
-
-        for h in &historico_mega_sela_list {
-            let mut contagem_ocorrencias: u8 = 0;
-
-            for numero in generated_mega_sena.set.clone() {
-                if h.set.contains(&numero) {
-                    contagem_ocorrencias += 1;
-                }
-            }
-
-            if PRINT_NAO_JOGAVEL && contagem_ocorrencias >= QTD_TOLERAVEL {
-                ocorrencias_encontradas = true;
-
-                println!(
-                    "\n\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
-                 🚫 JOGO BLOQUEADO\n\
-                 ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
-                 O jogo {} NÃO deve ser jogado.\n\
-                 ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n",
-                    generated_mega_sena
-                );
-
-                println!("Motivo:");
-                println!(
-                    "• Pelo menos {QTD_TOLERAVEL} números coincidem com um concurso anterior."
-                );
-                println!("• Concurso Nº: {}", h.concurso);
-                println!("• Bolas do concurso: {}", h);
-                println!("• Data: {}", h.data);
-                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-
-                break;
-            }
-        }
-
-        if !ocorrencias_encontradas {
-
-            let mut soma_jogo = 0;
-            for n in &generated_mega_sena.set {
-                soma_jogo+=n;
-            }
-            if soma_jogo > soma_minima && soma_jogo < soma_maxima {
-                jogos_jogaveis_desejados -= 1;
-                jogos_gerados.push(generated_mega_sena.clone());
-            }
-        }
+fn run(args: Args) -> Result<()> {
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| Error::Config(format!("--threads {}: {}", threads, e)))?;
     }
 
-    for jogo in jogos_gerados {
-        mega_sena_service::save(&mut conn, jogo.clone())?;
-        println!("{}",jogo);
-    }
+    let drift = if args.lenient {
+        DriftMode::Lenient
+    } else {
+        DriftMode::Strict
+    };
+    let snapshots =
+        (!args.no_snapshot).then(|| SnapshotPolicy::beside(&args.db, args.keep_snapshots.get()));
+    let ctx = Contexto {
+        db: args.db,
+        store_options: StoreOptions { drift, snapshots },
+    };
 
-    Ok(())
+    match args.command {
+        None => commands::generate::run(&ctx, args.generate),
+        Some(Command::Generate(generate)) => commands::generate::run(&ctx, generate),
+        Some(Command::Check(check)) => commands::check::run(&ctx, check),
+        Some(Command::Enumerate(enumerate)) => commands::enumerate::run(&ctx, enumerate),
+        Some(Command::Explain(explain)) => commands::explain::run(&ctx, explain),
+        Some(Command::Export(export)) => commands::export::run(&ctx, export),
+        Some(Command::Volante(volante)) => commands::volante::run(&ctx, volante),
+        Some(Command::Relatorio(relatorio)) => commands::relatorio::run(&ctx, relatorio),
+        Some(Command::Serve(serve)) => commands::serve::run(&ctx, serve),
+        Some(Command::Virada(virada)) => commands::virada::run(&ctx, virada),
+        Some(Command::Tui) => commands::tui::run(&ctx),
+        Some(Command::Migrate { command }) => commands::migrate::run(&ctx, command),
+        Some(Command::Backup(backup)) => commands::backup::backup(&ctx, backup),
+        Some(Command::Restore(restore)) => commands::backup::restore(&ctx, restore),
+        Some(Command::Flags { command }) => commands::flags::run(&ctx, command),
+    }
 }
@@ -0,0 +1,19 @@
+use chrono::{NaiveDate, ParseError};
+
+/// Formato usado pela Caixa nos CSVs e na coluna `data` (`dd/mm/yyyy`).
+pub const FORMATO_DATA_BR: &str = "%d/%m/%Y";
+
+/// Formato ISO usado na coluna `data_typesafe` (`yyyy-mm-dd`).
+pub const FORMATO_DATA_ISO: &str = "%Y-%m-%d";
+
+/// Converte uma data em `dd/mm/yyyy` ou `yyyy-mm-dd` para `NaiveDate`.
+pub fn parse_data(s: &str) -> Result<NaiveDate, ParseError> {
+    let s = s.trim();
+    NaiveDate::parse_from_str(s, FORMATO_DATA_BR)
+        .or_else(|_| NaiveDate::parse_from_str(s, FORMATO_DATA_ISO))
+}
+
+/// Formata a data no padrão brasileiro (`dd/mm/yyyy`).
+pub fn format_data_br(data: NaiveDate) -> String {
+    data.format(FORMATO_DATA_BR).to_string()
+}
@@ -0,0 +1,41 @@
+/// Converte um valor em reais para centavos.
+///
+/// Aceita o formato brasileiro (`R$ 1.234,56`) e o formato com ponto
+/// decimal (`1234.56`). Devolve `None` para texto vazio ou inválido.
+pub fn parse_reais(valor: &str) -> Option<u64> {
+    let limpo = valor.trim().trim_start_matches("R$").trim();
+    if limpo.is_empty() {
+        return None;
+    }
+
+    let (inteiro, fracao) = if limpo.contains(',') {
+        let sem_milhar = limpo.replace('.', "");
+        let (i, f) = sem_milhar.split_once(',')?;
+        (i.to_string(), f.to_string())
+    } else {
+        match limpo.split_once('.') {
+            Some((i, f)) => (i.to_string(), f.to_string()),
+            None => (limpo.to_string(), String::new()),
+        }
+    };
+
+    if fracao.len() > 2 || !fracao.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let reais: u64 = inteiro.parse().ok()?;
+    let centavos: u64 = format!("{:0<2}", fracao).parse().ok()?;
+    reais.checked_mul(100)?.checked_add(centavos)
+}
+
+/// Formata centavos como `R$ 1.234,56`.
+pub fn format_reais(centavos: u64) -> String {
+    let reais = (centavos / 100).to_string();
+    let mut agrupado = String::new();
+    for (i, c) in reais.chars().enumerate() {
+        if i > 0 && (reais.len() - i).is_multiple_of(3) {
+            agrupado.push('.');
+        }
+        agrupado.push(c);
+    }
+    format!("R$ {},{:02}", agrupado, centavos % 100)
+}
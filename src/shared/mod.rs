@@ -1 +1,3 @@
+pub mod date;
+pub mod dinheiro;
 pub mod sha3;
@@ -1,15 +1,10 @@
-use anyhow::{Context, Result};
-use chrono::Utc;
-use rand::seq::IteratorRandom;
-use rusqlite::{Connection, OptionalExtension, params};
 use sha3::{Digest, Sha3_256};
-use std::io::{BufReader, Read};
+use std::fs;
+use std::io::{self, BufReader, Read};
 use std::path::Path;
-use std::{fs, num};
 
-pub fn sha3_256_of_file(path: &Path) -> Result<String> {
-    let file = fs::File::open(path)
-        .with_context(|| format!("Falha ao abrir arquivo para checksum: {}", path.display()))?;
+pub fn sha3_256_of_file(path: &Path) -> io::Result<String> {
+    let file = fs::File::open(path)?;
     let mut reader = BufReader::new(file);
     let mut hasher = Sha3_256::new();
     let mut buffer = [0u8; 4096];
@@ -24,3 +19,7 @@ pub fn sha3_256_of_file(path: &Path) -> Result<String> {
 
     Ok(format!("{:x}", hasher.finalize()))
 }
+
+pub fn sha3_256_of_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha3_256::digest(bytes))
+}
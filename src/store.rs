@@ -0,0 +1,255 @@
+use std::path::Path;
+
+use chrono::NaiveDate;
+use rand::Rng;
+use rusqlite::Connection;
+
+use crate::core::mega_sena::{Bet, Draw};
+use crate::core::periodo::Periodo;
+use crate::database::csv;
+use crate::database::lotes::{self, Lote, SelecaoLotes};
+use crate::database::mega_sena_bootstrap::{self, Ingestao};
+use crate::database::migrations::{self, DriftMode, MigrationRun};
+use crate::database::mission_control::{self, Flag, FlagState};
+use crate::database::snapshot::SnapshotPolicy;
+use crate::engine::analyser::{self, Coincidencia};
+use crate::engine::conferencia::{self, Conferencia, Rateio};
+use crate::engine::enumeracao::{self, Enumeracao};
+use crate::engine::estatisticas::Estatisticas;
+use crate::engine::explain::{self, Explicacao};
+use crate::engine::mega_sena_service::{self, Estimativa, Evento, GeneratorConfig};
+use crate::engine::otimizador::{self, Otimizado};
+use crate::engine::popularidade::ModeloPopularidade;
+use crate::engine::virada::{self, PanoramaVirada};
+use crate::error::Result;
+
+/// Opções de abertura do [`Store`].
+#[derive(Debug, Clone, Default)]
+pub struct StoreOptions {
+    /// Como tratar migrations aplicadas cujo checksum mudou.
+    pub drift: DriftMode,
+    /// Snapshot automático antes de aplicar migrations pendentes.
+    pub snapshots: Option<SnapshotPolicy>,
+}
+
+/// Banco SQLite do gerador, com o schema já migrado.
+///
+/// É o ponto de entrada da biblioteca: abre o banco, importa concursos,
+/// gera e grava lotes, confere apostas e calcula estatísticas.
+pub struct Store {
+    conn: Connection,
+    /// O que foi feito ao abrir o banco.
+    abertura: MigrationRun,
+}
+
+impl Store {
+    /// Abre (ou cria) o banco em `path` e aplica as migrations pendentes.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with(path, &StoreOptions::default())
+    }
+
+    pub fn open_with(path: impl AsRef<Path>, options: &StoreOptions) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?, options)
+    }
+
+    /// Banco em memória, útil para testes e simulações.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?, &StoreOptions::default())
+    }
+
+    fn from_connection(conn: Connection, options: &StoreOptions) -> Result<Self> {
+        let abertura = match &options.snapshots {
+            Some(policy) => {
+                migrations::migrate_up_with_snapshot(&conn, None, options.drift, policy)?
+            }
+            None => MigrationRun {
+                snapshot: None,
+                executadas: migrations::migrate_up(&conn, None, options.drift)?,
+            },
+        };
+        Ok(Self { conn, abertura })
+    }
+
+    /// Migrations que estavam pendentes e foram aplicadas na abertura.
+    pub fn migracoes_aplicadas(&self) -> &[&'static str] {
+        &self.abertura.executadas
+    }
+
+    /// Snapshot gravado antes dessas migrations, conforme
+    /// [`StoreOptions::snapshots`].
+    pub fn snapshot_pre_migracao(&self) -> Option<&Path> {
+        self.abertura.snapshot.as_deref()
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn connection_mut(&mut self) -> &mut Connection {
+        &mut self.conn
+    }
+
+    /// Importa os concursos de um CSV e devolve quantos eram novos;
+    /// reimportar o mesmo arquivo não duplica concursos.
+    pub fn import_csv(&mut self, csv_path: impl AsRef<Path>) -> Result<usize> {
+        csv::ingest_csv_mega_sena_to_sqlite(&mut self.conn, csv_path.as_ref())
+    }
+
+    /// Importa o CSV apenas se a tabela de concursos ainda estiver vazia.
+    pub fn bootstrap_from_csv(&mut self, csv_path: impl AsRef<Path>) -> Result<Ingestao> {
+        mega_sena_bootstrap::bootstrap_mega_sena_data_from_csv(&mut self.conn, csv_path.as_ref())
+    }
+
+    pub fn historico(&self, periodo: &Periodo) -> Result<Vec<Draw>> {
+        Ok(analyser::listar_historico_mega_sena(&self.conn, periodo)?)
+    }
+
+    /// Gera um lote filtrado pelo histórico do período. Não grava nada.
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        periodo: &Periodo,
+        config: &GeneratorConfig,
+        rng: &mut R,
+        on_event: impl FnMut(Evento<'_>),
+    ) -> Result<Vec<Bet>> {
+        let historico = self.historico(periodo)?;
+        mega_sena_service::generate_batch(&historico, config, rng, on_event)
+    }
+
+    /// Estima por amostragem quantas combinações passam nos filtros de
+    /// `config` no período.
+    pub fn estimate<R: Rng + ?Sized>(
+        &self,
+        periodo: &Periodo,
+        config: &GeneratorConfig,
+        rng: &mut R,
+        amostras: u64,
+    ) -> Result<Estimativa> {
+        let historico = self.historico(periodo)?;
+        mega_sena_service::estimar_viabilidade(&historico, config, rng, amostras)
+    }
+
+    /// Gera um lote e o melhora segundo `config.otimizacao`, comparando o
+    /// resultado com lotes aleatórios.
+    pub fn optimize<R: Rng + ?Sized>(
+        &self,
+        periodo: &Periodo,
+        config: &GeneratorConfig,
+        rng: &mut R,
+        on_event: impl FnMut(Evento<'_>),
+    ) -> Result<Otimizado> {
+        let historico = self.historico(periodo)?;
+        otimizador::otimizar(&historico, config, rng, on_event)
+    }
+
+    /// Percorre todas as combinações com os filtros de `config`, contando os
+    /// sobreviventes de cada etapa, e sorteia até `amostra` jogos aprovados.
+    pub fn enumerate<R: Rng + ?Sized>(
+        &self,
+        periodo: &Periodo,
+        config: &GeneratorConfig,
+        rng: &mut R,
+        amostra: usize,
+    ) -> Result<Enumeracao> {
+        let historico = self.historico(periodo)?;
+        enumeracao::enumerar(&historico, config, rng, amostra)
+    }
+
+    /// Grava as apostas como um novo lote, registrando a configuração que
+    /// as gerou, e devolve o id do lote. Com `virada`, o lote já é gravado
+    /// marcado para a Mega da Virada daquele ano.
+    pub fn save_batch(
+        &mut self,
+        apostas: &[Bet],
+        config: &GeneratorConfig,
+        virada: Option<i32>,
+    ) -> Result<i64> {
+        mega_sena_service::save(&mut self.conn, apostas, config, virada)
+    }
+
+    /// Lotes gravados, com as apostas e a configuração que os gerou.
+    pub fn lotes(&self, selecao: &SelecaoLotes) -> Result<Vec<Lote>> {
+        lotes::listar(&self.conn, selecao)
+    }
+
+    /// Marca o lote como gerado para a Mega da Virada de `ano`.
+    pub fn marcar_virada(&self, lote_id: i64, ano: i32) -> Result<()> {
+        lotes::marcar_virada(&self.conn, lote_id, ano)
+    }
+
+    /// Concursos do período com pelo menos `minimo` bolas em comum com a aposta.
+    pub fn check_bet(
+        &self,
+        aposta: &Bet,
+        periodo: &Periodo,
+        minimo: u32,
+    ) -> Result<Vec<Coincidencia>> {
+        let historico = self.historico(periodo)?;
+        Ok(analyser::coincidencias(aposta, &historico, minimo))
+    }
+
+    /// Confere a aposta contra todo o período: acertos exatos, quinas e
+    /// quadras e quanto teria rendido. `padrao` dá o valor das faixas de
+    /// concursos importados sem rateio.
+    pub fn conferir(
+        &self,
+        aposta: &Bet,
+        periodo: &Periodo,
+        padrao: &Rateio,
+    ) -> Result<Conferencia> {
+        let historico = self.historico(periodo)?;
+        let rateios = analyser::listar_rateios(&self.conn, periodo)?;
+        Ok(conferencia::conferir(aposta, &historico, &rateios, padrao))
+    }
+
+    /// Explica como os filtros de `config` tratariam a aposta no período e
+    /// lista os concursos com pelo menos `minimo` bolas em comum.
+    pub fn explain(
+        &self,
+        aposta: &Bet,
+        periodo: &Periodo,
+        config: &GeneratorConfig,
+        minimo: u32,
+    ) -> Result<Explicacao> {
+        let historico = self.historico(periodo)?;
+        let filtros = config.filtros(&historico);
+        Ok(explain::explicar(aposta, &historico, &filtros, minimo))
+    }
+
+    /// Modelo de popularidade com os concursos recentes do período.
+    pub fn popularidade(&self, periodo: &Periodo) -> Result<ModeloPopularidade> {
+        Ok(ModeloPopularidade::new(&self.historico(periodo)?))
+    }
+
+    /// Valor efetivo da feature flag (o padrão, se não estiver gravada).
+    pub fn flag(&self, flag: Flag) -> Result<bool> {
+        mission_control::is_enabled(&self.conn, flag)
+    }
+
+    pub fn set_flag(&self, flag: Flag, enabled: bool) -> Result<()> {
+        mission_control::set(&self.conn, flag, enabled)
+    }
+
+    pub fn flags(&self) -> Result<Vec<FlagState>> {
+        mission_control::list(&self.conn)
+    }
+
+    pub fn stats(&self, periodo: &Periodo) -> Result<Estatisticas> {
+        Ok(Estatisticas::calcular(&self.historico(periodo)?))
+    }
+
+    /// Mega da Virada vista de `hoje`: a próxima data, as Viradas do
+    /// histórico com estatísticas só delas e os valores acumulados.
+    pub fn virada(&self, hoje: NaiveDate) -> Result<PanoramaVirada> {
+        virada::panorama(&self.conn, &self.historico(&Periodo::todos())?, hoje)
+    }
+
+    /// Configuração de geração padrão, com os filtros experimentais ligados
+    /// pelas flags.
+    pub fn generator_config(&self) -> Result<GeneratorConfig> {
+        Ok(GeneratorConfig {
+            paridade: self.flag(Flag::FiltroParidade)?.then_some((1, 5)),
+            ..GeneratorConfig::default()
+        })
+    }
+}
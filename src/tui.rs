@@ -0,0 +1,535 @@
+//! Interface interativa no terminal do comando `tui`: histórico,
+//! estatísticas e um gerador em que os filtros mudam com uma tecla e o lote
+//! é refeito na hora.
+//!
+//! [`Painel`] guarda o estado e desenha com `ratatui`; o laço de eventos fica
+//! no binário. Como tudo é texto, funciona também por SSH.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use ratatui::Frame;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState, Tabs};
+
+use crate::core::mega_sena::{Bet, Draw};
+use crate::core::periodo::Periodo;
+use crate::engine::estatisticas::Estatisticas;
+use crate::engine::mega_sena_service::{GeneratorConfig, Strategy};
+use crate::error::Result;
+use crate::shared::date;
+use crate::store::Store;
+
+/// Limite de tempo de cada geração, para a tela não travar com filtros
+/// impossíveis.
+const TEMPO_GERACAO: Duration = Duration::from_secs(2);
+
+const ESTRATEGIAS: [(Strategy, &str); 5] = [
+    (Strategy::Uniforme, "uniforme"),
+    (
+        Strategy::Frequencia {
+            janela: None,
+            inverso: false,
+        },
+        "frequência (quentes)",
+    ),
+    (
+        Strategy::Frequencia {
+            janela: None,
+            inverso: true,
+        },
+        "frequência (frias)",
+    ),
+    (
+        Strategy::Atraso {
+            janela: None,
+            inverso: false,
+        },
+        "atraso (atrasadas)",
+    ),
+    (
+        Strategy::Atraso {
+            janela: None,
+            inverso: true,
+        },
+        "atraso (recentes)",
+    ),
+];
+const PARIDADES: [Option<(u32, u32)>; 3] = [None, Some((1, 5)), Some((2, 4))];
+const ANTI_POPULAR: [Option<usize>; 3] = [None, Some(10), Some(50)];
+
+/// Abas do painel, na ordem das teclas `1`, `2` e `3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aba {
+    Historico,
+    Estatisticas,
+    Gerador,
+}
+
+impl Aba {
+    const TODAS: [Aba; 3] = [Aba::Historico, Aba::Estatisticas, Aba::Gerador];
+
+    fn titulo(self) -> &'static str {
+        match self {
+            Self::Historico => "1 Histórico",
+            Self::Estatisticas => "2 Estatísticas",
+            Self::Gerador => "3 Gerador",
+        }
+    }
+
+    fn indice(self) -> usize {
+        Self::TODAS.iter().position(|&a| a == self).unwrap_or(0)
+    }
+
+    /// A aba `passos` à direita, voltando à primeira depois da última.
+    fn avancar(self, passos: usize) -> Self {
+        Self::TODAS[(self.indice() + passos) % Self::TODAS.len()]
+    }
+}
+
+/// Ordem da tabela de estatísticas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ordem {
+    Bola,
+    Frequencia,
+    Atraso,
+}
+
+impl Ordem {
+    fn proxima(self) -> Self {
+        match self {
+            Self::Bola => Self::Frequencia,
+            Self::Frequencia => Self::Atraso,
+            Self::Atraso => Self::Bola,
+        }
+    }
+
+    fn descricao(self) -> &'static str {
+        match self {
+            Self::Bola => "por bola",
+            Self::Frequencia => "mais sorteadas primeiro",
+            Self::Atraso => "mais atrasadas primeiro",
+        }
+    }
+}
+
+/// O que o laço de eventos faz depois de uma tecla.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controle {
+    Continua,
+    Sai,
+}
+
+#[derive(Debug, Clone)]
+struct Jogo {
+    aposta: Bet,
+    guardado: bool,
+}
+
+/// Estado da interface.
+pub struct Painel {
+    store: Store,
+    rng: StdRng,
+    /// Em ordem de concurso.
+    historico: Vec<Draw>,
+    estatisticas: Estatisticas,
+    config: GeneratorConfig,
+    jogos: Vec<Jogo>,
+    aba: Aba,
+    ordem: Ordem,
+    tabela_historico: TableState,
+    tabela_estatisticas: TableState,
+    tabela_jogos: TableState,
+    mensagem: Option<String>,
+}
+
+impl Painel {
+    /// Carrega o histórico e gera o primeiro lote com a configuração padrão
+    /// do banco.
+    pub fn new(store: Store, rng: StdRng) -> Result<Self> {
+        let historico = store.historico(&Periodo::todos())?;
+        let estatisticas = Estatisticas::calcular(&historico);
+        let mut config = store.generator_config()?;
+        config.orcamento.tempo_limite.get_or_insert(TEMPO_GERACAO);
+
+        let mut painel = Self {
+            store,
+            rng,
+            historico,
+            estatisticas,
+            config,
+            jogos: Vec::new(),
+            aba: Aba::Historico,
+            ordem: Ordem::Bola,
+            tabela_historico: TableState::default().with_selected(Some(0)),
+            tabela_estatisticas: TableState::default().with_selected(Some(0)),
+            tabela_jogos: TableState::default().with_selected(Some(0)),
+            mensagem: None,
+        };
+        painel.regerar();
+        Ok(painel)
+    }
+
+    pub fn aba(&self) -> Aba {
+        self.aba
+    }
+
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    /// Jogos do lote atual e se estão marcados para guardar.
+    pub fn jogos(&self) -> impl Iterator<Item = (&Bet, bool)> {
+        self.jogos.iter().map(|j| (&j.aposta, j.guardado))
+    }
+
+    /// Resultado da última ação (lote gravado, erro de geração...).
+    pub fn mensagem(&self) -> Option<&str> {
+        self.mensagem.as_deref()
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Trata uma tecla pressionada.
+    pub fn tecla(&mut self, tecla: KeyEvent) -> Controle {
+        match tecla.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Controle::Sai,
+            KeyCode::Char('c') if tecla.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Controle::Sai;
+            }
+            KeyCode::Char(c @ '1'..='3') => {
+                self.aba = Aba::TODAS[c as usize - '1' as usize];
+            }
+            KeyCode::Tab => self.aba = self.aba.avancar(1),
+            KeyCode::BackTab => self.aba = self.aba.avancar(Aba::TODAS.len() - 1),
+            KeyCode::Up => self.rolar(-1),
+            KeyCode::Down => self.rolar(1),
+            KeyCode::PageUp => self.rolar(-10),
+            KeyCode::PageDown => self.rolar(10),
+            KeyCode::Home => self.rolar(isize::MIN),
+            KeyCode::End => self.rolar(isize::MAX),
+            KeyCode::Char(c) => match self.aba {
+                Aba::Estatisticas if c == 'o' => {
+                    self.ordem = self.ordem.proxima();
+                    self.tabela_estatisticas.select(Some(0));
+                }
+                Aba::Gerador => self.tecla_gerador(c),
+                _ => {}
+            },
+            _ => {}
+        }
+        Controle::Continua
+    }
+
+    fn tecla_gerador(&mut self, tecla: char) {
+        match tecla {
+            ' ' => {
+                if let Some(jogo) = self
+                    .tabela_jogos
+                    .selected()
+                    .and_then(|i| self.jogos.get_mut(i))
+                {
+                    jogo.guardado = !jogo.guardado;
+                }
+                return;
+            }
+            's' => return self.gravar(),
+            'g' => {}
+            '+' => self.config.quantidade = (self.config.quantidade + 1).min(50),
+            '-' => self.config.quantidade = self.config.quantidade.saturating_sub(1).max(1),
+            't' => self.config.qtd_toleravel = (self.config.qtd_toleravel + 1).min(6),
+            'T' => self.config.qtd_toleravel = self.config.qtd_toleravel.saturating_sub(1).max(3),
+            'e' => {
+                let atual = ESTRATEGIAS
+                    .iter()
+                    .position(|(s, _)| *s == self.config.strategy);
+                let proxima = atual.map_or(0, |i| (i + 1) % ESTRATEGIAS.len());
+                self.config.strategy = ESTRATEGIAS[proxima].0;
+            }
+            'p' => self.config.paridade = proximo(&PARIDADES, self.config.paridade),
+            'a' => self.config.anti_popular = proximo(&ANTI_POPULAR, self.config.anti_popular),
+            _ => return,
+        }
+        self.regerar();
+    }
+
+    /// Refaz o lote: os jogos marcados ficam e o resto é gerado de novo.
+    fn regerar(&mut self) {
+        let guardados: Vec<Jogo> = self.jogos.iter().filter(|j| j.guardado).cloned().collect();
+        let faltam = self.config.quantidade.saturating_sub(guardados.len());
+        self.jogos = guardados;
+        self.mensagem = None;
+        if faltam == 0 {
+            return;
+        }
+
+        // Pede a mais para descartar os que repetirem um jogo guardado.
+        let repetidos: HashSet<Bet> = self.jogos.iter().map(|j| j.aposta.clone()).collect();
+        let config = GeneratorConfig {
+            quantidade: faltam + repetidos.len(),
+            ..self.config.clone()
+        };
+        match self
+            .store
+            .generate(&Periodo::todos(), &config, &mut self.rng, |_| {})
+        {
+            Ok(novos) => self.jogos.extend(
+                novos
+                    .into_iter()
+                    .filter(|aposta| !repetidos.contains(aposta))
+                    .take(faltam)
+                    .map(|aposta| Jogo {
+                        aposta,
+                        guardado: false,
+                    }),
+            ),
+            Err(e) => self.mensagem = Some(e.to_string()),
+        }
+        let ultimo = self.jogos.len().saturating_sub(1);
+        let selecionado = self.tabela_jogos.selected().unwrap_or(0).min(ultimo);
+        self.tabela_jogos.select(Some(selecionado));
+    }
+
+    /// Grava os jogos marcados como um lote e desmarca todos.
+    fn gravar(&mut self) {
+        let apostas: Vec<Bet> = self
+            .jogos
+            .iter()
+            .filter(|j| j.guardado)
+            .map(|j| j.aposta.clone())
+            .collect();
+        if apostas.is_empty() {
+            self.mensagem = Some("marque os jogos com espaço antes de gravar".to_string());
+            return;
+        }
+
+        self.mensagem = Some(match self.store.save_batch(&apostas, &self.config, None) {
+            Ok(lote_id) => {
+                self.jogos.iter_mut().for_each(|j| j.guardado = false);
+                format!("Lote {} gravado com {} jogos", lote_id, apostas.len())
+            }
+            Err(e) => e.to_string(),
+        });
+    }
+
+    fn rolar(&mut self, passo: isize) {
+        let (tabela, linhas) = match self.aba {
+            Aba::Historico => (&mut self.tabela_historico, self.historico.len()),
+            Aba::Estatisticas => (
+                &mut self.tabela_estatisticas,
+                self.estatisticas.frequencia.len(),
+            ),
+            Aba::Gerador => (&mut self.tabela_jogos, self.jogos.len()),
+        };
+        if linhas == 0 {
+            return;
+        }
+        let atual = tabela.selected().unwrap_or(0);
+        let destino = atual.saturating_add_signed(passo).min(linhas - 1);
+        tabela.select(Some(destino));
+    }
+
+    /// Desenha a tela inteira.
+    pub fn desenhar(&mut self, frame: &mut Frame) {
+        let [topo, corpo, rodape] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let abas = Tabs::new(Aba::TODAS.map(Aba::titulo))
+            .select(self.aba.indice())
+            .highlight_style(Style::new().fg(Color::Green).add_modifier(Modifier::BOLD))
+            .block(Block::bordered().title(" Mega-Sena "));
+        frame.render_widget(abas, topo);
+
+        match self.aba {
+            Aba::Historico => self.desenhar_historico(frame, corpo),
+            Aba::Estatisticas => self.desenhar_estatisticas(frame, corpo),
+            Aba::Gerador => self.desenhar_gerador(frame, corpo),
+        }
+
+        let rodape_texto = match &self.mensagem {
+            Some(mensagem) => Line::from(mensagem.as_str()).yellow(),
+            None => Line::from(self.ajuda()).dark_gray(),
+        };
+        frame.render_widget(Paragraph::new(rodape_texto), rodape);
+    }
+
+    fn ajuda(&self) -> &'static str {
+        match self.aba {
+            Aba::Historico => "↑↓ PgUp PgDn rolam · 1-3/Tab trocam de aba · q sai",
+            Aba::Estatisticas => "↑↓ rolam · o muda a ordem · 1-3/Tab trocam de aba · q sai",
+            Aba::Gerador => "↑↓ escolhem · espaço marca · g regera · s grava os marcados · q sai",
+        }
+    }
+
+    fn desenhar_historico(&mut self, frame: &mut Frame, area: Rect) {
+        let linhas = self.historico.iter().rev().map(|draw| {
+            let bolas: Vec<String> = draw.bolas().iter().map(|b| b.to_string()).collect();
+            let pares = draw.bolas().iter().filter(|b| b.value() % 2 == 0).count();
+            Row::new([
+                draw.concurso.to_string(),
+                date::format_data_br(draw.data),
+                bolas.join(" "),
+                draw.soma().to_string(),
+                pares.to_string(),
+            ])
+        });
+        let tabela = Table::new(
+            linhas,
+            [
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(17),
+                Constraint::Length(4),
+                Constraint::Length(5),
+            ],
+        )
+        .header(Row::new(["Concurso", "Data", "Bolas", "Soma", "Pares"]).bold())
+        .row_highlight_style(Style::new().reversed())
+        .block(Block::bordered().title(format!(
+            " {} concursos, do mais recente ao mais antigo ",
+            self.historico.len()
+        )));
+        frame.render_stateful_widget(tabela, area, &mut self.tabela_historico);
+    }
+
+    fn desenhar_estatisticas(&mut self, frame: &mut Frame, area: Rect) {
+        let stats = &self.estatisticas;
+        let [resumo, tabela_area] =
+            Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).areas(area);
+
+        let soma = match (stats.soma_minima, stats.soma_maxima) {
+            (Some(min), Some(max)) => format!("soma entre {} e {}", min, max),
+            _ => "sem concursos importados".to_string(),
+        };
+        let pares: Vec<String> = stats
+            .pares
+            .iter()
+            .enumerate()
+            .map(|(i, n)| format!("{}: {}", i, n))
+            .collect();
+        let texto = vec![
+            Line::from(format!("{} concursos · {}", stats.concursos, soma)),
+            Line::from(format!("Concursos por bolas pares — {}", pares.join(" · "))),
+        ];
+        frame.render_widget(
+            Paragraph::new(texto).block(Block::bordered().title(" Resumo ")),
+            resumo,
+        );
+
+        let mut bolas: Vec<usize> = (0..stats.frequencia.len()).collect();
+        match self.ordem {
+            Ordem::Bola => {}
+            Ordem::Frequencia => bolas.sort_by_key(|&i| std::cmp::Reverse(stats.frequencia[i])),
+            Ordem::Atraso => bolas.sort_by_key(|&i| std::cmp::Reverse(stats.atraso[i])),
+        }
+        let maior = stats.frequencia.iter().copied().max().unwrap_or(0).max(1);
+        let linhas = bolas.into_iter().map(|i| {
+            let barra = "█".repeat((stats.frequencia[i] * 30 / maior) as usize);
+            Row::new([
+                Line::from(format!("{:02}", i + 1)),
+                Line::from(stats.frequencia[i].to_string()).right_aligned(),
+                Line::from(Span::styled(barra, Style::new().fg(Color::Green))),
+                Line::from(stats.atraso[i].to_string()).right_aligned(),
+            ])
+        });
+        let tabela = Table::new(
+            linhas,
+            [
+                Constraint::Length(4),
+                Constraint::Length(10),
+                Constraint::Length(30),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(["Bola", "Frequência", "", "Atraso"]).bold())
+        .row_highlight_style(Style::new().reversed())
+        .block(Block::bordered().title(format!(" Bolas, {} ", self.ordem.descricao())));
+        frame.render_stateful_widget(tabela, tabela_area, &mut self.tabela_estatisticas);
+    }
+
+    fn desenhar_gerador(&mut self, frame: &mut Frame, area: Rect) {
+        let [filtros, jogos] =
+            Layout::horizontal([Constraint::Length(38), Constraint::Min(0)]).areas(area);
+
+        let config = &self.config;
+        let estrategia = ESTRATEGIAS
+            .iter()
+            .find(|(s, _)| *s == config.strategy)
+            .map_or(config.strategy.nome(), |(_, nome)| nome);
+        let paridade = config
+            .paridade
+            .map_or("desligada".to_string(), |(min, max)| {
+                format!("{} a {} pares", min, max)
+            });
+        let anti_popular = config
+            .anti_popular
+            .map_or("desligado".to_string(), |n| format!("{} candidatos", n));
+        let opcao = |tecla: &'static str, nome: &'static str, valor: String| {
+            Line::from(vec![
+                Span::styled(format!("{:>3} ", tecla), Style::new().fg(Color::Cyan)),
+                Span::raw(format!("{}: ", nome)),
+                Span::raw(valor).bold(),
+            ])
+        };
+        let texto = vec![
+            opcao("+/-", "Jogos", config.quantidade.to_string()),
+            opcao(
+                "t/T",
+                "Tolerância",
+                format!("{} bolas", config.qtd_toleravel),
+            ),
+            opcao("e", "Estratégia", estrategia.to_string()),
+            opcao("p", "Paridade", paridade),
+            opcao("a", "Anti-popular", anti_popular),
+        ];
+        frame.render_widget(
+            Paragraph::new(texto).block(Block::bordered().title(" Filtros ")),
+            filtros,
+        );
+
+        let guardados = self.jogos.iter().filter(|j| j.guardado).count();
+        let linhas = self.jogos.iter().enumerate().map(|(i, jogo)| {
+            let marca = if jogo.guardado { "[x]" } else { "[ ]" };
+            let linha = Row::new([
+                marca.to_string(),
+                (i + 1).to_string(),
+                jogo.aposta.join(" "),
+                jogo.aposta.soma().to_string(),
+            ]);
+            if jogo.guardado { linha.green() } else { linha }
+        });
+        let tabela = Table::new(
+            linhas,
+            [
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(17),
+                Constraint::Length(4),
+            ],
+        )
+        .header(Row::new(["", "#", "Números", "Soma"]).bold())
+        .row_highlight_style(Style::new().reversed())
+        .block(Block::bordered().title(format!(
+            " {} jogos, {} marcados ",
+            self.jogos.len(),
+            guardados
+        )));
+        frame.render_stateful_widget(tabela, jogos, &mut self.tabela_jogos);
+    }
+}
+
+/// Próximo valor de `opcoes` depois de `atual`, voltando ao primeiro.
+fn proximo<T: Copy + PartialEq>(opcoes: &[T], atual: T) -> T {
+    let i = opcoes.iter().position(|&o| o == atual);
+    opcoes[i.map_or(0, |i| (i + 1) % opcoes.len())]
+}
@@ -0,0 +1,205 @@
+use mega_da_virada_generator::Store;
+use mega_da_virada_generator::api;
+use serde_json::json;
+
+mod common;
+
+use common::CSV;
+
+#[test]
+fn routes_import_generate_list_and_check() {
+    let mut store = Store::open_in_memory().unwrap();
+
+    let importacao = api::atender(&mut store, "POST", "/import", CSV.as_bytes());
+    assert_eq!(importacao.status, 200);
+    assert_eq!(importacao.corpo, json!({ "importados": 3 }));
+
+    let stats = api::atender(&mut store, "GET", "/stats?de=18%2F03%2F1996", b"");
+    assert_eq!(stats.status, 200);
+    assert_eq!(stats.corpo["concursos"], 2);
+
+    let pedido = json!({ "config": { "quantidade": 3 }, "seed": 5 }).to_string();
+    let gerado = api::atender(&mut store, "POST", "/generate", pedido.as_bytes());
+    assert_eq!(gerado.status, 200, "{}", gerado.corpo);
+    assert_eq!(gerado.corpo["jogos"].as_array().unwrap().len(), 3);
+    assert!(gerado.corpo["otimizacao"].is_null());
+    let lote_id = gerado.corpo["lote_id"].as_i64().unwrap();
+    let repetido = json!({ "config": { "quantidade": 3 }, "seed": 5, "simular": true });
+    let simulado = api::atender(
+        &mut store,
+        "POST",
+        "/generate",
+        repetido.to_string().as_bytes(),
+    );
+    assert_eq!(simulado.corpo["jogos"], gerado.corpo["jogos"]);
+    assert!(simulado.corpo["lote_id"].is_null());
+
+    let lotes = api::atender(&mut store, "GET", "/lotes", b"");
+    assert_eq!(lotes.corpo.as_array().unwrap().len(), 1);
+    let lote = api::atender(&mut store, "GET", &format!("/lotes/{}", lote_id), b"");
+    assert_eq!(lote.corpo["apostas"].as_array().unwrap().len(), 3);
+
+    let conferencia = api::atender(&mut store, "GET", "/check?numeros=10,11,29,30,36,47", b"");
+    assert_eq!(conferencia.status, 200);
+    assert_eq!(conferencia.corpo["concursos_conferidos"], 3);
+    assert_eq!(
+        conferencia.corpo["premiacoes"][0]["concurso"]["concurso"],
+        3
+    );
+}
+
+#[test]
+fn importing_the_same_csv_twice_adds_nothing() {
+    let mut store = Store::open_in_memory().unwrap();
+
+    let primeira = api::atender(&mut store, "POST", "/import", CSV.as_bytes());
+    assert_eq!(primeira.corpo, json!({ "importados": 3 }));
+    let segunda = api::atender(&mut store, "POST", "/import", CSV.as_bytes());
+    assert_eq!(segunda.status, 200);
+    assert_eq!(segunda.corpo, json!({ "importados": 0 }));
+
+    let stats = api::atender(&mut store, "GET", "/stats", b"");
+    assert_eq!(stats.corpo["concursos"], 3);
+
+    // Um CSV mais novo só acrescenta o concurso que faltava.
+    let novo = format!("{}4,01/04/1996,1,2,3,4,5,6\n", CSV);
+    let terceira = api::atender(&mut store, "POST", "/import", novo.as_bytes());
+    assert_eq!(terceira.corpo, json!({ "importados": 1 }));
+}
+
+#[test]
+fn generate_caps_client_supplied_limits() {
+    let mut store = common::store_em_memoria();
+
+    let pedido = json!({
+        "config": {
+            "quantidade": 1_000_000_000_u64,
+            "orcamento": { "max_tentativas": null, "tempo_limite": null },
+        },
+        "seed": 1,
+        "simular": true,
+    });
+    let gerado = api::atender(
+        &mut store,
+        "POST",
+        "/generate",
+        pedido.to_string().as_bytes(),
+    );
+    assert_eq!(gerado.status, 200, "{}", gerado.corpo);
+    let config = &gerado.corpo["config"];
+    assert_eq!(config["quantidade"], api::MAX_JOGOS);
+    assert_eq!(config["orcamento"]["max_tentativas"], api::MAX_TENTATIVAS);
+    assert_eq!(
+        config["orcamento"]["tempo_limite"]["secs"],
+        api::TEMPO_GERACAO.as_secs()
+    );
+    assert_eq!(
+        gerado.corpo["jogos"].as_array().unwrap().len(),
+        api::MAX_JOGOS
+    );
+
+    let pedido = json!({
+        "config": {
+            "quantidade": 3,
+            "otimizacao": {
+                "objetivo": "cobertura_pares",
+                "iteracoes": u64::MAX,
+                "tempo_limite": { "secs": 0, "nanos": 50_000_000 },
+                "lotes_aleatorios": 1_000_000_000_u64,
+            },
+        },
+        "seed": 1,
+        "simular": true,
+    });
+    let gerado = api::atender(
+        &mut store,
+        "POST",
+        "/generate",
+        pedido.to_string().as_bytes(),
+    );
+    assert_eq!(gerado.status, 200, "{}", gerado.corpo);
+    let otimizacao = &gerado.corpo["config"]["otimizacao"];
+    assert_eq!(otimizacao["iteracoes"], api::MAX_ITERACOES);
+    assert_eq!(otimizacao["lotes_aleatorios"], api::MAX_LOTES_ALEATORIOS);
+    assert_eq!(otimizacao["tempo_limite"]["nanos"], 50_000_000);
+
+    // O lote devolvido passou mesmo pela busca.
+    let resumo = &gerado.corpo["otimizacao"];
+    assert_eq!(resumo["objetivo"], "cobertura_pares");
+    assert!(resumo["iteracoes"].as_u64().unwrap() <= api::MAX_ITERACOES);
+    assert!(resumo["aleatorio"].is_number());
+    assert_eq!(gerado.corpo["jogos"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn generate_rejects_unknown_fields_without_saving() {
+    let mut store = common::store_em_memoria();
+
+    let pedidos = [
+        json!({ "quantidade": 3 }),
+        json!({ "config": { "quantidad": 3 } }),
+        json!({ "config": { "orcamento": { "tempo": 1 } } }),
+        json!({ "config": { "restricoes": { "fixo": [7] } } }),
+        json!({ "config": { "otimizacao": {
+            "objetivo": "equilibrio",
+            "iteracoes": 10,
+            "tempo_limite": null,
+            "lotes_aleatorios": 0,
+            "iteracao": 1,
+        } } }),
+        json!({ "config": { "strategy": {
+            "nome": "frequencia",
+            "janela": 5,
+            "inverso": false,
+            "jenela": 5,
+        } } }),
+    ];
+    for pedido in pedidos {
+        let resposta = api::atender(
+            &mut store,
+            "POST",
+            "/generate",
+            pedido.to_string().as_bytes(),
+        );
+        assert_eq!(resposta.status, 400, "{}", pedido);
+    }
+
+    let lotes = api::atender(&mut store, "GET", "/lotes", b"");
+    assert!(lotes.corpo.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn errors_map_to_http_status_codes() {
+    let mut store = Store::open_in_memory().unwrap();
+    let status = |store: &mut Store, metodo: &str, caminho: &str, corpo: &[u8]| {
+        api::atender(store, metodo, caminho, corpo).status
+    };
+
+    assert_eq!(status(&mut store, "GET", "/nada", b""), 404);
+    assert_eq!(status(&mut store, "DELETE", "/lotes", b""), 405);
+    assert_eq!(status(&mut store, "GET", "/lotes/7", b""), 404);
+    assert_eq!(status(&mut store, "GET", "/lotes/abc", b""), 400);
+    assert_eq!(status(&mut store, "GET", "/check?numeros=1,2,3", b""), 400);
+    assert_eq!(status(&mut store, "GET", "/stats?de=ontem", b""), 400);
+    assert_eq!(status(&mut store, "POST", "/generate", b"{"), 400);
+    assert_eq!(
+        status(
+            &mut store,
+            "POST",
+            "/import",
+            b"concurso,data\n1,x,1,2,3,4,5,6\n"
+        ),
+        400
+    );
+
+    // Com 1 e 3 fixos, nenhum jogo tem seis números pares.
+    let impossivel = json!({ "config": { "restricoes": { "fixos": [1, 3] }, "paridade": [6, 6] } });
+    let resposta = api::atender(
+        &mut store,
+        "POST",
+        "/generate",
+        impossivel.to_string().as_bytes(),
+    );
+    assert_eq!(resposta.status, 422, "{}", resposta.corpo);
+    assert_eq!(resposta.corpo["codigo"], 14);
+}
@@ -0,0 +1,135 @@
+use mega_da_virada_generator::database::migrations::{self, DriftMode};
+use mega_da_virada_generator::database::snapshot::{self, SnapshotPolicy};
+use mega_da_virada_generator::database::sqlite;
+use mega_da_virada_generator::{GeneratorConfig, Store, StoreOptions};
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+fn contar_lotes(conn: &Connection) -> i64 {
+    conn.query_row("SELECT COUNT(*) FROM t_lote", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn backup_and_restore_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let mut store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+    store
+        .save_batch(
+            &["1 2 3 4 5 6".parse().unwrap()],
+            &GeneratorConfig::default(),
+            None,
+        )
+        .unwrap();
+
+    let backup = dir.path().join("backup.db");
+    sqlite::backup_to_file(store.connection(), &backup).unwrap();
+
+    store
+        .save_batch(
+            &["7 8 9 10 11 12".parse().unwrap()],
+            &GeneratorConfig::default(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(contar_lotes(store.connection()), 2);
+
+    sqlite::restore_from_file(store.connection_mut(), &backup).unwrap();
+    assert_eq!(contar_lotes(store.connection()), 1);
+
+    assert!(sqlite::restore_from_file(store.connection_mut(), &dir.path().join("nao.db")).is_err());
+    assert_eq!(contar_lotes(store.connection()), 1);
+}
+
+#[test]
+fn snapshots_keep_only_the_most_recent() {
+    let dir = TempDir::new().unwrap();
+    let conn = Connection::open(dir.path().join("mega_sena.db")).unwrap();
+    let policy = SnapshotPolicy::beside(&dir.path().join("mega_sena.db"), 2);
+
+    let primeiro = snapshot::take(&conn, &policy, "manual").unwrap();
+    let segundo = snapshot::take(&conn, &policy, "manual").unwrap();
+    let terceiro = snapshot::take(&conn, &policy, "manual").unwrap();
+
+    assert_eq!(policy.dir, dir.path().join("snapshots"));
+    assert!(!primeiro.exists());
+    assert_eq!(
+        snapshot::list_for(&conn, &policy.dir).unwrap(),
+        vec![segundo, terceiro]
+    );
+}
+
+#[test]
+fn keeping_zero_snapshots_still_keeps_the_one_just_taken() {
+    let dir = TempDir::new().unwrap();
+    let conn = Connection::open(dir.path().join("mega_sena.db")).unwrap();
+    let policy = SnapshotPolicy::beside(&dir.path().join("mega_sena.db"), 0);
+
+    let primeiro = snapshot::take(&conn, &policy, "manual").unwrap();
+    let segundo = snapshot::take(&conn, &policy, "pre-migration").unwrap();
+
+    assert!(!primeiro.exists());
+    assert!(segundo.exists());
+    assert_eq!(
+        snapshot::list_for(&conn, &policy.dir).unwrap(),
+        vec![segundo]
+    );
+}
+
+#[test]
+fn snapshots_are_listed_only_for_their_own_database() {
+    let dir = TempDir::new().unwrap();
+    let mega = Connection::open(dir.path().join("mega.db")).unwrap();
+    let mega_sena = Connection::open(dir.path().join("mega-sena.db")).unwrap();
+    let policy = SnapshotPolicy::beside(&dir.path().join("mega.db"), 1);
+
+    let de_mega_sena = snapshot::take(&mega_sena, &policy, "manual").unwrap();
+    let de_mega = snapshot::take(&mega, &policy, "manual").unwrap();
+
+    assert!(de_mega_sena.exists());
+    assert_eq!(
+        snapshot::list_for(&mega, &policy.dir).unwrap(),
+        vec![de_mega]
+    );
+    assert_eq!(
+        snapshot::list_for(&mega_sena, &policy.dir).unwrap(),
+        vec![de_mega_sena]
+    );
+}
+
+#[test]
+fn migration_runner_snapshots_only_when_something_is_pending() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("mega_sena.db");
+    let conn = Connection::open(&db).unwrap();
+    let penultima = migrations::MIGRATIONS[migrations::MIGRATIONS.len() - 2].file_name;
+    migrations::migrate_up(&conn, Some(penultima), DriftMode::Strict).unwrap();
+    drop(conn);
+
+    let options = StoreOptions {
+        snapshots: Some(SnapshotPolicy::beside(&db, 5)),
+        ..StoreOptions::default()
+    };
+    let store = Store::open_with(&db, &options).unwrap();
+    let policy = options.snapshots.as_ref().unwrap();
+    let snapshots = snapshot::list_for(store.connection(), &policy.dir).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(store.snapshot_pre_migracao(), Some(snapshots[0].as_path()));
+
+    // O snapshot é do banco antes da última migration.
+    let anterior = Connection::open(&snapshots[0]).unwrap();
+    assert_eq!(
+        migrations::applied_migrations(&anterior).unwrap().len(),
+        migrations::MIGRATIONS.len() - 1
+    );
+
+    drop(store);
+    let store = Store::open_with(&db, &options).unwrap();
+    assert!(store.snapshot_pre_migracao().is_none());
+    assert_eq!(
+        snapshot::list_for(store.connection(), &policy.dir)
+            .unwrap()
+            .len(),
+        1
+    );
+}
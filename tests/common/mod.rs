@@ -0,0 +1,49 @@
+//! Dados compartilhados pelos testes de integração.
+
+// Cada arquivo de teste é um crate à parte e usa só parte destes itens.
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::Write;
+
+use mega_da_virada_generator::{Draw, Periodo, Store};
+use tempfile::TempDir;
+
+/// Os três primeiros concursos da Mega-Sena, do mais recente para o mais
+/// antigo, como no arquivo da Caixa.
+pub const CSV: &str = "\
+concurso,data,bola_1,bola_2,bola_3,bola_4,bola_5,bola_6
+3,25/03/1996,10,11,29,30,36,47
+2,18/03/1996,9,37,39,41,43,49
+1,11/03/1996,4,5,30,33,41,52
+";
+
+/// Importa [`CSV`] no banco.
+pub fn importar(store: &mut Store) {
+    let mut csv = tempfile::NamedTempFile::new().unwrap();
+    csv.write_all(CSV.as_bytes()).unwrap();
+    assert_eq!(store.import_csv(csv.path()).unwrap(), 3);
+}
+
+/// Banco em memória com os concursos de [`CSV`].
+pub fn store_em_memoria() -> Store {
+    let mut store = Store::open_in_memory().unwrap();
+    importar(&mut store);
+    store
+}
+
+/// Banco `mega_sena.db` em `dir`, com os concursos de [`CSV`] importados de
+/// `mega_sena.csv` no mesmo diretório.
+pub fn store_com_historico(dir: &TempDir) -> Store {
+    let csv_path = dir.path().join("mega_sena.csv");
+    fs::write(&csv_path, CSV).unwrap();
+
+    let mut store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+    assert_eq!(store.import_csv(&csv_path).unwrap(), 3);
+    store
+}
+
+/// Os concursos de [`CSV`], em ordem de concurso.
+pub fn historico() -> Vec<Draw> {
+    store_em_memoria().historico(&Periodo::todos()).unwrap()
+}
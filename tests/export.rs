@@ -0,0 +1,172 @@
+use chrono::NaiveDate;
+use mega_da_virada_generator::export::relatorio;
+use mega_da_virada_generator::export::volante::{self, Cabecalho};
+use mega_da_virada_generator::export::{self, Formato};
+use mega_da_virada_generator::{Bet, GeneratorConfig, Periodo, SelecaoLotes, Store, Strategy};
+
+mod common;
+
+fn exportar(store: &Store, selecao: &SelecaoLotes, formato: Formato) -> String {
+    let mut saida = Vec::new();
+    export::exportar(&store.lotes(selecao).unwrap(), formato, &mut saida).unwrap();
+    String::from_utf8(saida).unwrap()
+}
+
+#[test]
+fn batches_are_exported_with_their_parameters() {
+    let mut store = Store::open_in_memory().unwrap();
+    let config = GeneratorConfig {
+        strategy: Strategy::Frequencia {
+            janela: Some(50),
+            inverso: true,
+        },
+        ..GeneratorConfig::default()
+    };
+    let primeiro = store
+        .save_batch(
+            &[
+                "1 2 3 4 5 6".parse().unwrap(),
+                "7 8 9 10 11 12".parse().unwrap(),
+            ],
+            &config,
+            None,
+        )
+        .unwrap();
+    let segundo = store
+        .save_batch(&["13 14 15 16 17 18".parse().unwrap()], &config, None)
+        .unwrap();
+
+    let ultimo = store.lotes(&SelecaoLotes::Ultimo).unwrap();
+    assert_eq!(ultimo.len(), 1);
+    assert_eq!(ultimo[0].id, segundo);
+    assert_eq!(
+        store
+            .lotes(&SelecaoLotes::Periodo(Periodo::todos()))
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(store.lotes(&SelecaoLotes::Ids(vec![999])).is_err());
+
+    let ambos = SelecaoLotes::Ids(vec![primeiro, segundo]);
+    let csv = exportar(&store, &ambos, Formato::Csv);
+    let linhas: Vec<&str> = csv.lines().collect();
+    assert_eq!(linhas.len(), 4);
+    assert!(linhas[0].starts_with("lote_id,lote_uuid,criado_em,strategy,config"));
+    assert!(linhas[1].starts_with(&format!("{},", primeiro)));
+    assert!(linhas[1].contains(",frequencia,"));
+    assert!(linhas[2].ends_with(",07 08 09 10 11 12"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&exportar(&store, &ambos, Formato::Json)).unwrap();
+    assert_eq!(
+        json[0]["apostas"][1]["numeros"],
+        serde_json::json!([7, 8, 9, 10, 11, 12])
+    );
+    assert!(json[0]["criado_em"].is_string());
+    assert_eq!(
+        serde_json::from_value::<GeneratorConfig>(json[1]["config"].clone()).unwrap(),
+        config
+    );
+
+    let markdown = exportar(&store, &ambos, Formato::Markdown);
+    assert!(markdown.contains(&format!("### Lote {} · ", segundo)));
+    assert!(markdown.contains("Estratégia: `frequencia`"));
+    assert!(markdown.contains("`qtd_toleravel: 4`"));
+    assert!(markdown.contains("| 2 | 07 08 09 10 11 12 |"));
+}
+
+#[test]
+fn volantes_mark_the_numbers_and_the_bet_size() {
+    let jogos: Vec<Bet> = vec![
+        "5 17 23 38 44 60".parse().unwrap(),
+        "1 2 3 4 5 6 7 8".parse().unwrap(),
+    ];
+    let cabecalho = Cabecalho {
+        bolao: Some("Firma <TI> & (amigos)".to_string()),
+        lote: Some(3),
+    };
+
+    let svg = volante::svg(&jogos[1], 2, 2, &cabecalho);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("Bolão: Firma &lt;TI&gt; &amp; (amigos)"));
+    assert!(svg.contains("Lote 3 · Jogo 2 de 2"));
+    let centralizados = svg.matches(r#"text-anchor="middle""#).count();
+    assert_eq!(centralizados, 60 + 15);
+    // Oito números e o quadro de tamanho 8 marcados.
+    let marcados = svg
+        .matches(r#"font-weight="bold" text-anchor="middle""#)
+        .count();
+    assert_eq!(marcados, 9);
+    assert!(svg.contains(r#"font-weight="bold" text-anchor="middle">08</text>"#));
+
+    let pdf = volante::pdf(&jogos, &cabecalho);
+    let texto = String::from_utf8_lossy(&pdf);
+    assert!(texto.starts_with("%PDF-1.4\n"));
+    assert!(texto.ends_with("%%EOF\n"));
+    assert!(texto.contains("/Count 2 >>"));
+    assert_eq!(texto.matches("/Type /Page ").count(), 2);
+    assert!(pdf.windows(12).any(|w| w == b"Firma <TI> &"));
+    assert!(texto.contains(r"\(amigos\)"));
+
+    // A tabela xref aponta para o início de cada objeto (em bytes: o texto
+    // em WinAnsi não é UTF-8).
+    let inicio_xref: usize = texto
+        .rsplit("startxref\n")
+        .next()
+        .and_then(|fim| fim.lines().next())
+        .unwrap()
+        .parse()
+        .unwrap();
+    let xref = std::str::from_utf8(&pdf[inicio_xref..]).unwrap();
+    assert!(xref.starts_with("xref\n"));
+    for (i, linha) in xref
+        .lines()
+        .skip(3)
+        .take_while(|l| l.ends_with(" n "))
+        .enumerate()
+    {
+        let deslocamento: usize = linha[..10].parse().unwrap();
+        assert!(pdf[deslocamento..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+    }
+}
+
+#[test]
+fn report_is_self_contained_and_shows_the_batch() {
+    let historico = common::historico();
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .save_batch(
+            &["4 5 30 33 41 60".parse().unwrap()],
+            &GeneratorConfig::default(),
+            None,
+        )
+        .unwrap();
+    let lote = store.lotes(&SelecaoLotes::Ultimo).unwrap().remove(0);
+    let cabecalho = relatorio::Cabecalho {
+        titulo: "Bolão <da firma>".to_string(),
+        periodo: Periodo::new(Some(historico[0].data), None),
+        so_viradas: false,
+        gerado_em: NaiveDate::from_ymd_opt(2026, 12, 1).unwrap(),
+    };
+
+    let html = relatorio::html(&historico, Some(&lote), &cabecalho);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>Bolão &lt;da firma&gt;</h1>"));
+    assert!(html.contains("3 concursos · a partir de 11/03/1996 · gerado em 01/12/2026"));
+    for secao in ["Frequência", "Atraso", "Soma das bolas", "Pares e ímpares"] {
+        assert!(html.contains(&format!("<h2>{}</h2>", secao)), "{}", secao);
+    }
+    assert_eq!(html.matches("<svg").count(), 4);
+    assert!(!html.contains("<script") && !html.contains("<link") && !html.contains("src="));
+
+    // Últimos resultados do mais recente para o mais antigo.
+    assert!(html.find("<td>3</td>").unwrap() < html.find("<td>1</td>").unwrap());
+    // O jogo do lote acertou cinco bolas do concurso 1.
+    assert!(html.contains(&format!("<p>Lote {}", lote.id)));
+    assert!(html.contains("<td class=\"numero\">5</td></tr>"));
+
+    let vazio = relatorio::html(&[], None, &cabecalho);
+    assert!(vazio.contains("Nenhum concurso no período."));
+    assert!(vazio.contains("Nenhum lote gravado."));
+}
@@ -0,0 +1,73 @@
+use mega_da_virada_generator::{Bet, Filtros, Flag, Rejeicao, Store};
+
+#[test]
+fn flags_fall_back_to_defaults_and_persist_changes() {
+    let store = Store::open_in_memory().unwrap();
+
+    assert!(!store.flag(Flag::PrintNaoJogavel).unwrap());
+    store.set_flag(Flag::PrintNaoJogavel, true).unwrap();
+    assert!(store.flag(Flag::PrintNaoJogavel).unwrap());
+    store.set_flag(Flag::PrintNaoJogavel, false).unwrap();
+    assert!(!store.flag(Flag::PrintNaoJogavel).unwrap());
+
+    let registros: i64 = store
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM t_mission_control WHERE key = 'print_nao_jogavel'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(registros, 1);
+}
+
+#[test]
+fn list_reads_untyped_values_and_unknown_keys() {
+    let store = Store::open_in_memory().unwrap();
+    store
+        .connection()
+        .execute_batch(
+            "INSERT INTO t_mission_control (key, enable) VALUES ('filtro_paridade', 'true');
+             INSERT INTO t_mission_control (key, enable) VALUES ('legado', 1);",
+        )
+        .unwrap();
+
+    assert!(store.flag(Flag::FiltroParidade).unwrap());
+
+    let estados = store.flags().unwrap();
+    let print = estados
+        .iter()
+        .find(|e| e.key == "print_nao_jogavel")
+        .unwrap();
+    assert!(!print.enabled && !print.from_database);
+    let legado = estados.iter().find(|e| e.key == "legado").unwrap();
+    assert_eq!(legado.flag, None);
+    assert!(legado.enabled);
+
+    assert!("nao_existe".parse::<Flag>().is_err());
+    assert_eq!(
+        "filtro_paridade".parse::<Flag>().unwrap(),
+        Flag::FiltroParidade
+    );
+}
+
+#[test]
+fn parity_filter_rejects_games_outside_the_range() {
+    let filtros = Filtros {
+        qtd_toleravel: 6,
+        soma: None,
+        paridade: Some((1, 5)),
+    };
+
+    let so_pares: Bet = "02,04,06,08,10,12".parse().unwrap();
+    assert_eq!(
+        filtros.avaliar(&so_pares, &[]),
+        Err(Rejeicao::Paridade {
+            pares: 6,
+            min: 1,
+            max: 5
+        })
+    );
+    let misto: Bet = "01,04,06,08,10,12".parse().unwrap();
+    assert_eq!(filtros.avaliar(&misto, &[]), Ok(()));
+}
@@ -0,0 +1,428 @@
+use std::fs;
+
+use mega_da_virada_generator::Store;
+use mega_da_virada_generator::database::migrations::{
+    self, DriftMode, MIGRATIONS, Migration, MigrationState,
+};
+use mega_da_virada_generator::database::schema::{self, SchemaChange};
+use mega_da_virada_generator::shared::sha3;
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+fn arquivos_sql() -> Vec<String> {
+    let mut arquivos: Vec<String> = fs::read_dir("migrations")
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|f| f.ends_with(".sql"))
+        .collect();
+    arquivos.sort();
+    arquivos
+}
+
+#[test]
+fn every_sql_file_is_embedded_in_order() {
+    let ups: Vec<String> = arquivos_sql()
+        .into_iter()
+        .filter(|f| !f.ends_with(".down.sql"))
+        .collect();
+
+    let embutidas: Vec<&str> = MIGRATIONS.iter().map(|m| m.file_name).collect();
+    assert_eq!(ups, embutidas);
+}
+
+#[test]
+fn every_down_file_is_paired_with_its_migration() {
+    for down in arquivos_sql().iter().filter(|f| f.ends_with(".down.sql")) {
+        let up = down.replace(".down.sql", ".sql");
+        let migration = MIGRATIONS.iter().find(|m| m.file_name == up).unwrap();
+        let conteudo = fs::read_to_string(format!("migrations/{}", down)).unwrap();
+        assert_eq!(migration.down, Some(conteudo.as_str()), "{}", down);
+    }
+}
+
+#[test]
+fn embedded_checksum_matches_file_checksum() {
+    for m in MIGRATIONS {
+        let path = format!("migrations/{}", m.file_name);
+        assert_eq!(
+            m.checksum(),
+            sha3::sha3_256_of_file(path.as_ref()).unwrap(),
+            "{}",
+            m.file_name
+        );
+    }
+}
+
+#[test]
+fn up_to_stops_at_the_given_migration() {
+    let conn = Connection::open_in_memory().unwrap();
+
+    let executadas = migrations::migrate_up(
+        &conn,
+        Some("0004_reboot_mega_sena_generation.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+    assert_eq!(executadas.len(), 5);
+
+    let status = migrations::status(&conn).unwrap();
+    assert!(matches!(status[4].state, MigrationState::Applied { .. }));
+    assert_eq!(status[5].state, MigrationState::Pending);
+
+    let restantes = migrations::migrate_up(&conn, None, DriftMode::Strict).unwrap();
+    assert_eq!(restantes.len(), MIGRATIONS.len() - 5);
+    assert!(
+        migrations::migrate_up(&conn, None, DriftMode::Strict)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn verify_detects_checksum_mismatch() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::run_migrations(&conn).unwrap();
+    migrations::verify(&conn).unwrap();
+
+    conn.execute(
+        "UPDATE t_migration SET checksum_sha3 = 'abc' WHERE file_name = '0003_add_indexes.sql'",
+        [],
+    )
+    .unwrap();
+
+    assert!(migrations::verify(&conn).is_err());
+    let status = migrations::status(&conn).unwrap();
+    assert!(matches!(
+        &status[3].state,
+        MigrationState::ChecksumMismatch { stored, .. } if stored == "abc"
+    ));
+    assert!(migrations::migrate_up(&conn, None, DriftMode::Strict).is_err());
+}
+
+#[test]
+fn unknown_migration_is_an_error() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert!(migrations::migrate_up(&conn, Some("9999_nope.sql"), DriftMode::Strict).is_err());
+}
+
+fn tabela_existe(conn: &Connection, nome: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [nome],
+        |row| row.get::<_, i64>(0),
+    )
+    .unwrap()
+        > 0
+}
+
+#[test]
+fn failing_migration_leaves_no_trace() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::create_migration_table(&conn).unwrap();
+
+    let quebrada = Migration {
+        file_name: "9999_quebrada.sql",
+        sql: "CREATE TABLE t_parcial (id INTEGER); INSERT INTO tabela_inexistente VALUES (1);",
+        down: None,
+    };
+    assert!(migrations::apply(&conn, &quebrada, &quebrada.checksum()).is_err());
+
+    assert!(!tabela_existe(&conn, "t_parcial"));
+    assert!(migrations::applied_migrations(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn rollback_reverts_last_migrations_and_up_reapplies_them() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::run_migrations(&conn).unwrap();
+    conn.execute(
+        "INSERT INTO t_mega_sena (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6)
+         VALUES (1, '11/03/1996', '1996-03-11', 4, 5, 30, 33, 41, 52)",
+        [],
+    )
+    .unwrap();
+    conn.execute("INSERT INTO t_lote (uuid) VALUES ('lote-1')", [])
+        .unwrap();
+    conn.execute(
+        "INSERT INTO t_aposta (lote_id, numeros) VALUES (1, '01,02,03,04,05,06')",
+        [],
+    )
+    .unwrap();
+
+    // Desfaz tudo a partir de 0008_backfill_data_typesafe.sql.
+    let n = MIGRATIONS.len() - 8;
+    let desfeitas = migrations::rollback(&conn, n).unwrap();
+    let esperadas: Vec<&str> = MIGRATIONS[8..].iter().rev().map(|m| m.file_name).collect();
+    assert_eq!(desfeitas, esperadas);
+    assert_eq!(esperadas.last(), Some(&"0008_backfill_data_typesafe.sql"));
+    assert!(!tabela_existe(&conn, "t_aposta"));
+    let geradas: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM t_mega_sena WHERE generated_by_rust = 1 AND bola_6 = 6",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(geradas, 1);
+
+    let reaplicadas = migrations::migrate_up(&conn, None, DriftMode::Strict).unwrap();
+    assert_eq!(reaplicadas.len(), n);
+    let apostas: String = conn
+        .query_row("SELECT numeros FROM t_aposta", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(apostas, "01,02,03,04,05,06");
+    migrations::verify(&conn).unwrap();
+}
+
+#[test]
+fn rollback_without_down_script_changes_nothing() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::run_migrations(&conn).unwrap();
+
+    assert!(migrations::rollback(&conn, MIGRATIONS.len()).is_err());
+    assert_eq!(
+        migrations::applied_migrations(&conn).unwrap().len(),
+        MIGRATIONS.len()
+    );
+}
+
+fn banco_com_drift() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_up(
+        &conn,
+        Some("0004_reboot_mega_sena_generation.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+    conn.execute(
+        "UPDATE t_migration SET checksum_sha3 = 'abc' WHERE file_name = '0003_add_indexes.sql'",
+        [],
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn lenient_mode_applies_pending_despite_drift() {
+    let conn = banco_com_drift();
+
+    assert!(migrations::migrate_up(&conn, None, DriftMode::Strict).is_err());
+    let executadas = migrations::migrate_up(&conn, None, DriftMode::Lenient).unwrap();
+    assert_eq!(executadas.len(), MIGRATIONS.len() - 5);
+    assert!(migrations::verify(&conn).is_err());
+}
+
+#[test]
+fn drift_report_compares_stored_embedded_and_disk_checksums() {
+    let conn = banco_com_drift();
+
+    let report = migrations::drift_report(&conn, Some("migrations".as_ref())).unwrap();
+    assert_eq!(report.len(), 1);
+    let drift = &report[0];
+    assert_eq!(drift.file_name, "0003_add_indexes.sql");
+    assert_eq!(drift.stored, "abc");
+    assert_eq!(drift.embedded, MIGRATIONS[3].checksum());
+    assert_eq!(drift.on_disk.as_deref(), Some(drift.embedded.as_str()));
+    assert!(drift.is_recorded());
+
+    let sem_disco = migrations::drift_report(&conn, Some("nao_existe".as_ref())).unwrap();
+    assert_eq!(sem_disco[0].on_disk, None);
+}
+
+#[test]
+fn repair_records_embedded_checksums() {
+    let conn = banco_com_drift();
+
+    assert_eq!(
+        migrations::repair(&conn).unwrap(),
+        vec!["0003_add_indexes.sql"]
+    );
+    migrations::verify(&conn).unwrap();
+    assert!(migrations::drift_report(&conn, None).unwrap().is_empty());
+    assert!(migrations::repair(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn dry_run_reports_schema_changes_without_touching_the_database() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_up(
+        &conn,
+        Some("0004_reboot_mega_sena_generation.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+
+    let ensaio = migrations::dry_run(
+        &conn,
+        Some("0006_add_column_generated_games.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ensaio.executadas,
+        vec![
+            "0005_add_data_typesafe_column.sql",
+            "0006_add_column_generated_games.sql"
+        ]
+    );
+    let colunas: Vec<String> = ensaio
+        .mudancas
+        .iter()
+        .map(|m| match m {
+            SchemaChange::ColumnAdded { table, column } => format!("{}.{}", table, column.name),
+            outra => panic!("mudança inesperada: {}", outra),
+        })
+        .collect();
+    assert_eq!(
+        colunas,
+        vec!["t_mega_sena.data_typesafe", "t_mega_sena.generated_by_rust"]
+    );
+
+    assert_eq!(migrations::applied_migrations(&conn).unwrap().len(), 5);
+    assert!(
+        !schema::introspect(&conn).unwrap().tables["t_mega_sena"]
+            .iter()
+            .any(|c| c.name == "data_typesafe")
+    );
+}
+
+#[test]
+fn schema_diff_detects_tables_columns_and_indexes() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE a (id INTEGER PRIMARY KEY, x TEXT);
+         CREATE TABLE b (id INTEGER);
+         CREATE INDEX idx_a_x ON a (x);",
+    )
+    .unwrap();
+    let antes = schema::introspect(&conn).unwrap();
+
+    conn.execute_batch(
+        "DROP TABLE b;
+         CREATE TABLE c (id INTEGER);
+         ALTER TABLE a ADD COLUMN y INTEGER NOT NULL DEFAULT 0;
+         DROP INDEX idx_a_x;
+         CREATE UNIQUE INDEX idx_a_y ON a (y);",
+    )
+    .unwrap();
+    let depois = schema::introspect(&conn).unwrap();
+
+    let mudancas: Vec<String> = schema::diff(&antes, &depois)
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+    assert_eq!(
+        mudancas,
+        vec![
+            "+ coluna a.y INTEGER NOT NULL DEFAULT 0",
+            "- tabela b",
+            "+ tabela c (id INTEGER)",
+            "- índice idx_a_x em a",
+            "+ índice único idx_a_y em a(y)",
+        ]
+    );
+}
+
+#[test]
+fn unique_concurso_migration_merges_duplicated_draws() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_up(
+        &conn,
+        Some("0012_add_mega_da_virada.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+
+    conn.execute_batch(
+        "INSERT INTO t_mega_sena
+            (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6,
+             rateio_sena_centavos)
+         VALUES (1, '11/03/1996', '1996-03-11', 4, 5, 30, 33, 41, 52, NULL),
+                (1, '11/03/1996', '1996-03-11', 4, 5, 30, 33, 41, 52, 123400),
+                (2, '18/03/1996', '1996-03-18', 9, 37, 39, 41, 43, 49, NULL);",
+    )
+    .unwrap();
+
+    migrations::migrate_up(&conn, None, DriftMode::Strict).unwrap();
+
+    let linhas: Vec<(u32, Option<i64>)> = conn
+        .prepare("SELECT concurso, rateio_sena_centavos FROM t_mega_sena ORDER BY concurso")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(linhas, vec![(1, Some(123400)), (2, None)]);
+
+    let repetido = conn.execute(
+        "INSERT INTO t_mega_sena (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6)
+         VALUES (2, '18/03/1996', '1996-03-18', 9, 37, 39, 41, 43, 49)",
+        [],
+    );
+    assert!(repetido.is_err());
+}
+
+#[test]
+fn store_reports_migrations_applied_on_open() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("mega_sena.db");
+
+    let novo = Store::open(&db).unwrap();
+    assert_eq!(novo.migracoes_aplicadas().len(), MIGRATIONS.len());
+    drop(novo);
+
+    assert!(Store::open(&db).unwrap().migracoes_aplicadas().is_empty());
+}
+
+#[test]
+fn lote_migration_keeps_generated_games_without_a_parsed_date() {
+    let conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_up(
+        &conn,
+        Some("0008_backfill_data_typesafe.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+
+    conn.execute_batch(
+        "INSERT INTO t_mega_sena (concurso, data, data_typesafe, bola_1, bola_2, bola_3, bola_4, bola_5, bola_6,
+                                  generated_by_rust, inserted_at)
+         VALUES ('a', '11/03/1996', '1996-03-11', 1, 2, 3, 4, 5, 6, 1, '1996-03-11 10:00:00'),
+                ('b', '2024-01-05', NULL, 7, 8, 9, 10, 11, 12, 1, '2024-01-05 10:00:00'),
+                ('c', '?', NULL, 13, 14, 15, 16, 17, 18, 1, NULL);",
+    )
+    .unwrap();
+
+    migrations::migrate_up(
+        &conn,
+        Some("0009_create_lote_aposta.sql"),
+        DriftMode::Strict,
+    )
+    .unwrap();
+
+    let apostas: Vec<(String, Option<String>)> = conn
+        .prepare(
+            "SELECT a.numeros, l.created_at FROM t_aposta a JOIN t_lote l ON l.id = a.lote_id
+             ORDER BY a.numeros",
+        )
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        apostas,
+        vec![
+            (
+                "01,02,03,04,05,06".to_string(),
+                Some("1996-03-11".to_string())
+            ),
+            (
+                "07,08,09,10,11,12".to_string(),
+                Some("2024-01-05".to_string())
+            ),
+            ("13,14,15,16,17,18".to_string(), None),
+        ]
+    );
+}
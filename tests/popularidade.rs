@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+use mega_da_virada_generator::engine::mega_sena_service;
+use mega_da_virada_generator::engine::popularidade::Componente;
+use mega_da_virada_generator::{Bet, Draw, GeneratorConfig, ModeloPopularidade};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn bet(s: &str) -> Bet {
+    s.parse().unwrap()
+}
+
+fn pontos(modelo: &ModeloPopularidade, jogo: &str, componente: Componente) -> f64 {
+    modelo
+        .pontuar(&bet(jogo))
+        .componentes
+        .into_iter()
+        .find(|(c, _)| *c == componente)
+        .unwrap()
+        .1
+}
+
+#[test]
+fn popular_patterns_score_higher_than_a_spread_game() {
+    let ultimo = Draw::new(
+        10,
+        NaiveDate::from_ymd_opt(2024, 1, 6).unwrap(),
+        bet("08 19 27 38 45 52").bolas().to_vec(),
+    )
+    .unwrap();
+    let modelo = ModeloPopularidade::new(&[ultimo]);
+
+    assert_eq!(
+        pontos(&modelo, "01 07 12 19 25 31", Componente::Calendario),
+        30.0
+    );
+    assert_eq!(
+        pontos(&modelo, "05 10 15 20 25 30", Componente::Sequencias),
+        24.0
+    );
+    assert_eq!(
+        pontos(&modelo, "11 12 13 14 15 16", Componente::Sequencias),
+        30.0
+    );
+    // Coluna inteira do volante: 03, 13, …, 53.
+    assert_eq!(
+        pontos(&modelo, "03 13 23 33 43 53", Componente::Volante),
+        24.0
+    );
+    assert_eq!(
+        pontos(&modelo, "08 19 27 38 45 52", Componente::Recentes),
+        30.0
+    );
+
+    assert_eq!(modelo.pontuar(&bet("04 17 29 36 50 58")).total, 0.0);
+}
+
+#[test]
+fn anti_popular_mode_lowers_the_average_score() {
+    let modelo = ModeloPopularidade::new(&[]);
+    let media = |anti_popular: Option<usize>| {
+        let config = GeneratorConfig {
+            quantidade: 200,
+            qtd_toleravel: 6,
+            anti_popular,
+            ..GeneratorConfig::default()
+        };
+        let mut rng = StdRng::seed_from_u64(11);
+        let jogos = mega_sena_service::generate_batch(&[], &config, &mut rng, |_| {}).unwrap();
+        jogos.iter().map(|j| modelo.pontuar(j).total).sum::<f64>() / jogos.len() as f64
+    };
+
+    assert!(media(Some(10)) < media(None) / 2.0);
+}
@@ -0,0 +1,694 @@
+use std::collections::HashMap;
+use std::fs;
+
+use chrono::NaiveDate;
+use mega_da_virada_generator::engine::mega_sena_service::{self, Amostrador};
+use mega_da_virada_generator::engine::{analyser, conferencia, enumeracao};
+use mega_da_virada_generator::{
+    Ball, Bet, Error, Evento, Faixa, GeneratorConfig, Ingestao, Objetivo, Orcamento, Otimizacao,
+    Periodo, Rateio, Rejeicao, Restricoes, Store, Strategy,
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{CSV, store_com_historico};
+
+fn data(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn bootstrap_reports_what_it_did() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("mega_sena.csv");
+    let mut store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+
+    assert_eq!(
+        store.bootstrap_from_csv(&csv_path).unwrap(),
+        Ingestao::SemArquivo
+    );
+    fs::write(&csv_path, CSV).unwrap();
+    assert_eq!(
+        store.bootstrap_from_csv(&csv_path).unwrap(),
+        Ingestao::Importados(3)
+    );
+    assert_eq!(
+        store.bootstrap_from_csv(&csv_path).unwrap(),
+        Ingestao::JaPopulado
+    );
+}
+
+#[test]
+fn import_rejects_csv_without_the_six_balls() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("curto.csv");
+    fs::write(
+        &csv_path,
+        "concurso,data,bola_1,bola_2,bola_3,bola_4,bola_5\n1,11/03/1996,4,5,30,33,41\n",
+    )
+    .unwrap();
+
+    let mut store = Store::open_in_memory().unwrap();
+    let resultado = store.import_csv(&csv_path);
+    assert!(
+        matches!(&resultado, Err(Error::Import { motivo, .. }) if motivo.contains("colunas insuficientes")),
+        "{:?}",
+        resultado
+    );
+    assert!(store.historico(&Periodo::todos()).unwrap().is_empty());
+}
+
+#[test]
+fn import_and_list_history_by_period() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+
+    let todos = store.historico(&Periodo::todos()).unwrap();
+    assert_eq!(
+        todos.iter().map(|d| d.concurso).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(todos[0].data, data("1996-03-11"));
+
+    let periodo = Periodo::new(Some(data("1996-03-15")), Some(data("1996-03-20")));
+    let filtrados = store.historico(&periodo).unwrap();
+    assert_eq!(filtrados.len(), 1);
+    assert_eq!(filtrados[0].concurso, 2);
+}
+
+#[test]
+fn reopening_an_existing_database_keeps_data() {
+    let dir = TempDir::new().unwrap();
+    drop(store_com_historico(&dir));
+
+    let store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+    assert_eq!(store.historico(&Periodo::todos()).unwrap().len(), 3);
+}
+
+#[test]
+fn generated_batch_passes_filters_and_is_saved() {
+    let dir = TempDir::new().unwrap();
+    let mut store = store_com_historico(&dir);
+    let historico = store.historico(&Periodo::todos()).unwrap();
+
+    let config = GeneratorConfig {
+        quantidade: 5,
+        qtd_toleravel: 3,
+        ..GeneratorConfig::default()
+    };
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut rejeitados = 0;
+    let jogos = store
+        .generate(&Periodo::todos(), &config, &mut rng, |evento| {
+            if let Evento::Rejeitado(..) = evento {
+                rejeitados += 1
+            }
+        })
+        .unwrap();
+
+    assert_eq!(jogos.len(), 5);
+    for jogo in &jogos {
+        assert!(historico.iter().all(|d| jogo.acertos(d) < 3), "{}", jogo);
+        let soma = jogo.soma();
+        assert!(soma > 163 && soma < 218, "soma {} fora do intervalo", soma);
+    }
+    assert!(rejeitados > 0);
+
+    let lote_id = store.save_batch(&jogos, &config, None).unwrap();
+    let salvos: i64 = store
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM t_aposta WHERE lote_id = ?1",
+            [lote_id],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(salvos, 5);
+}
+
+#[test]
+fn every_rejection_reported_is_a_real_filter_failure() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let historico = store.historico(&Periodo::todos()).unwrap();
+
+    // Só jogos com seis bolas pares passam: cerca de 1% dos candidatos, então
+    // há rejeições com qualquer semente.
+    let config = GeneratorConfig {
+        quantidade: 3,
+        paridade: Some((6, 6)),
+        ..GeneratorConfig::default()
+    };
+    let filtros = config.filtros(&historico);
+    let mut rejeicoes = Vec::new();
+    let jogos = store
+        .generate(
+            &Periodo::todos(),
+            &config,
+            &mut StdRng::seed_from_u64(1),
+            |evento| {
+                if let Evento::Rejeitado(jogo, rejeicao) = evento {
+                    rejeicoes.push((jogo.clone(), rejeicao.clone()));
+                }
+            },
+        )
+        .unwrap();
+
+    assert_eq!(jogos.len(), 3);
+    for jogo in &jogos {
+        assert_eq!(filtros.avaliar(jogo, &historico), Ok(()));
+    }
+    assert!(!rejeicoes.is_empty());
+    for (jogo, rejeicao) in &rejeicoes {
+        assert_eq!(filtros.avaliar(jogo, &historico).as_ref(), Err(rejeicao));
+    }
+}
+
+#[test]
+fn weighted_strategies_favor_hot_or_overdue_numbers_and_are_recorded() {
+    let dir = TempDir::new().unwrap();
+    let mut store = store_com_historico(&dir);
+    let historico = store.historico(&Periodo::todos()).unwrap();
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // Na janela do último concurso, as bolas do concurso 3 pesam 2 e as demais 1.
+    let quentes = Strategy::Frequencia {
+        janela: Some(1),
+        inverso: false,
+    }
+    .amostrador(&historico);
+    let frias = Strategy::Frequencia {
+        janela: Some(1),
+        inverso: true,
+    }
+    .amostrador(&historico);
+    let concurso_3: Bet = "10 11 29 30 36 47".parse().unwrap();
+    let contar = |amostrador: &Amostrador, rng: &mut StdRng| -> u32 {
+        (0..2_000)
+            .map(|_| {
+                let jogo = amostrador.sample(rng).unwrap();
+                jogo.bolas()
+                    .iter()
+                    .filter(|b| concurso_3.contains(**b))
+                    .count() as u32
+            })
+            .sum()
+    };
+    assert!(contar(&quentes, &mut rng) > 2 * contar(&frias, &mut rng));
+
+    let config = GeneratorConfig {
+        quantidade: 1,
+        qtd_toleravel: 6,
+        strategy: Strategy::Atraso {
+            janela: None,
+            inverso: false,
+        },
+        ..GeneratorConfig::default()
+    };
+    let jogos = store
+        .generate(&Periodo::todos(), &config, &mut rng, |_| {})
+        .unwrap();
+    let lote_id = store.save_batch(&jogos, &config, None).unwrap();
+    let (strategy, gravada): (String, String) = store
+        .connection()
+        .query_row(
+            "SELECT strategy, config FROM t_lote WHERE id = ?1",
+            [lote_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(strategy, "atraso");
+    assert_eq!(
+        serde_json::from_str::<GeneratorConfig>(&gravada).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn check_bet_reports_shared_numbers() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+
+    let aposta: Bet = "4 5 30 33 41 52".parse().unwrap();
+    let coincidencias = store.check_bet(&aposta, &Periodo::todos(), 1).unwrap();
+
+    assert_eq!(coincidencias.len(), 3);
+    assert_eq!(coincidencias[0].concurso.concurso, 1);
+    assert_eq!(coincidencias[0].acertos(), 6);
+    assert_eq!(coincidencias[2].concurso.concurso, 3);
+    assert_eq!(
+        coincidencias[2]
+            .bolas_em_comum
+            .iter()
+            .map(|b| b.value())
+            .collect::<Vec<_>>(),
+        vec![30]
+    );
+}
+
+#[test]
+fn stats_count_frequency_delay_and_sums() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+
+    let stats = store.stats(&Periodo::todos()).unwrap();
+    assert_eq!(stats.concursos, 3);
+    assert_eq!(stats.frequencia[29], 2); // bola 30
+    assert_eq!(stats.frequencia[40], 2); // bola 41
+    assert_eq!(stats.atraso[3], 2); // bola 4 saiu só no concurso 1
+    assert_eq!(stats.atraso[9], 0); // bola 10 saiu no último
+    assert_eq!(stats.atraso[0], 3); // bola 1 nunca saiu
+    assert_eq!(stats.soma_minima, Some(163));
+    assert_eq!(stats.soma_maxima, Some(218));
+    assert_eq!(stats.pares.iter().sum::<u32>(), 3);
+}
+
+#[test]
+fn invalid_games_are_rejected() {
+    assert!("1 2 3 4 5".parse::<Bet>().is_err());
+    assert!("1 2 3 4 5 61".parse::<Bet>().is_err());
+    assert!("1 2 3 4 5 5".parse::<Bet>().is_err());
+}
+
+#[test]
+fn explain_lists_every_overlap_and_every_failing_filter() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let aposta: Bet = "04 05 10 11 30 33".parse().unwrap();
+
+    let explicacao = store
+        .explain(&aposta, &Periodo::todos(), &GeneratorConfig::default(), 2)
+        .unwrap();
+
+    assert!(!explicacao.jogavel);
+    assert_eq!(
+        explicacao
+            .coincidencias
+            .iter()
+            .map(|c| (c.concurso.concurso, c.acertos()))
+            .collect::<Vec<_>>(),
+        vec![(1, 4), (3, 3)]
+    );
+    assert_eq!(
+        explicacao
+            .filtros
+            .iter()
+            .map(|r| (r.filtro, r.aprovado))
+            .collect::<Vec<_>>(),
+        vec![("soma", false), ("sobreposicao_historico", false)]
+    );
+}
+
+#[test]
+fn check_reports_prizes_with_imported_or_default_values() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("mega_sena.csv");
+    fs::write(
+        &csv_path,
+        "\
+concurso,data,bola_1,bola_2,bola_3,bola_4,bola_5,bola_6,rateio_sena,rateio_quina,rateio_quadra
+2,18/03/1996,9,37,39,41,43,49,,,
+1,11/03/1996,4,5,30,33,41,52,\"R$ 1.000.000,00\",\"5.000,00\",\"100,50\"
+",
+    )
+    .unwrap();
+    let mut store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+    store.import_csv(&csv_path).unwrap();
+
+    // Sete números com a sena do concurso 1: 1 sena e 6 quinas.
+    let aposta: Bet = "04 05 30 33 41 52 60".parse().unwrap();
+    let conferencia = store
+        .conferir(&aposta, &Periodo::todos(), &Rateio::default())
+        .unwrap();
+    assert_eq!(conferencia.exatos().count(), 1);
+    assert_eq!(
+        conferencia.totais,
+        vec![(Faixa::Sena, 1), (Faixa::Quina, 6), (Faixa::Quadra, 0)]
+    );
+    assert_eq!(conferencia.total, 100_000_000 + 6 * 500_000);
+    assert_eq!(conferencia.sem_valor, 0);
+
+    // Quadra no concurso 2, que não tem rateio: só entra com valor padrão.
+    let quadra: Bet = "09 37 39 41 01 02".parse().unwrap();
+    let sem_valor = store
+        .conferir(&quadra, &Periodo::todos(), &Rateio::default())
+        .unwrap();
+    assert_eq!(sem_valor.premiacoes.len(), 1);
+    assert_eq!((sem_valor.total, sem_valor.sem_valor), (0, 1));
+
+    let padrao = Rateio {
+        quadra: Some(1_000),
+        ..Rateio::default()
+    };
+    let com_padrao = store.conferir(&quadra, &Periodo::todos(), &padrao).unwrap();
+    assert_eq!((com_padrao.total, com_padrao.sem_valor), (1_000, 0));
+}
+
+#[test]
+fn fixed_and_excluded_numbers_are_respected_and_validated() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let bolas = |s: &str| {
+        s.split(',')
+            .map(|n| n.parse().unwrap())
+            .collect::<Vec<Ball>>()
+    };
+    let mut rng = StdRng::seed_from_u64(3);
+
+    let config = GeneratorConfig {
+        quantidade: 20,
+        qtd_toleravel: 6,
+        restricoes: Restricoes {
+            fixos: bolas("7,13"),
+            excluidos: bolas("1,2,3,60"),
+        },
+        ..GeneratorConfig::default()
+    };
+    let jogos = store
+        .generate(&Periodo::todos(), &config, &mut rng, |_| {})
+        .unwrap();
+    for jogo in &jogos {
+        assert!(jogo.contains(bolas("7")[0]) && jogo.contains(bolas("13")[0]));
+        assert!(bolas("1,2,3,60").iter().all(|b| !jogo.contains(*b)));
+    }
+
+    let invalidas = [
+        Restricoes {
+            fixos: bolas("7"),
+            excluidos: bolas("7"),
+        },
+        Restricoes {
+            fixos: bolas("1,2,3,4,5,6,7"),
+            excluidos: vec![],
+        },
+        Restricoes {
+            fixos: vec![],
+            excluidos: Ball::all().skip(5).collect(),
+        },
+    ];
+    for restricoes in invalidas {
+        assert!(restricoes.validar().is_err(), "{:?}", restricoes);
+    }
+
+    // Quatro fixos do concurso 1 esbarram no filtro de histórico.
+    let bloqueada = GeneratorConfig {
+        restricoes: Restricoes {
+            fixos: bolas("4,5,30,33"),
+            excluidos: vec![],
+        },
+        ..GeneratorConfig::default()
+    };
+    assert!(matches!(
+        store.generate(&Periodo::todos(), &bloqueada, &mut rng, |_| {}),
+        Err(Error::Generation(_))
+    ));
+
+    // Só sobram bolas altas, e a soma passaria do máximo histórico (218).
+    let soma_impossivel = GeneratorConfig {
+        qtd_toleravel: 6,
+        restricoes: Restricoes {
+            fixos: bolas("50"),
+            excluidos: Ball::all().take(40).collect(),
+        },
+        ..GeneratorConfig::default()
+    };
+    assert!(matches!(
+        store.generate(&Periodo::todos(), &soma_impossivel, &mut rng, |_| {}),
+        Err(Error::Generation(_))
+    ));
+}
+
+#[test]
+fn generation_gives_up_when_the_budget_runs_out() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let config = GeneratorConfig {
+        quantidade: 1_000,
+        orcamento: Orcamento {
+            max_tentativas: Some(100),
+            tempo_limite: None,
+        },
+        ..GeneratorConfig::default()
+    };
+
+    let mut progresso = 0;
+    let resultado = store.generate(
+        &Periodo::todos(),
+        &config,
+        &mut StdRng::seed_from_u64(1),
+        |evento| {
+            if let Evento::Progresso(_) = evento {
+                progresso += 1;
+            }
+        },
+    );
+    assert!(matches!(resultado, Err(Error::Generation(m)) if m.contains("100 tentativas")));
+    assert_eq!(progresso, 0);
+}
+
+#[test]
+fn feasibility_estimate_counts_rejections_per_filter() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let mut excluidos: Vec<Ball> = store
+        .historico(&Periodo::todos())
+        .unwrap()
+        .iter()
+        .flat_map(|d| d.bolas().to_vec())
+        .collect();
+    excluidos.sort();
+    excluidos.dedup();
+
+    let config = GeneratorConfig {
+        qtd_toleravel: 1,
+        restricoes: Restricoes {
+            fixos: vec![],
+            excluidos,
+        },
+        ..GeneratorConfig::default()
+    };
+    let estimativa = store
+        .estimate(
+            &Periodo::todos(),
+            &config,
+            &mut StdRng::seed_from_u64(5),
+            2_000,
+        )
+        .unwrap();
+
+    // Sem as 16 bolas já sorteadas, só o filtro de soma pode rejeitar.
+    assert_eq!(estimativa.espaco, 7_059_052);
+    assert_eq!(
+        estimativa.rejeicoes.keys().collect::<Vec<_>>(),
+        vec![&"soma"]
+    );
+    assert_eq!(estimativa.aprovadas + estimativa.rejeicoes["soma"], 2_000);
+    assert!(estimativa.aprovadas > 0);
+    assert!(!estimativa.inviavel(&config));
+}
+
+#[test]
+fn enumeration_matches_filter_evaluation_on_every_combination() {
+    let dir = TempDir::new().unwrap();
+    let store = store_com_historico(&dir);
+    let historico = store.historico(&Periodo::todos()).unwrap();
+    let fixos = vec![Ball::new(30).unwrap(), Ball::new(41).unwrap()];
+    let config = GeneratorConfig {
+        qtd_toleravel: 3,
+        paridade: Some((2, 4)),
+        restricoes: Restricoes {
+            fixos: fixos.clone(),
+            excluidos: (50..=60).map(|n: u8| n.try_into().unwrap()).collect(),
+        },
+        ..GeneratorConfig::default()
+    };
+
+    let enumeracao = store
+        .enumerate(&Periodo::todos(), &config, &mut StdRng::seed_from_u64(9), 5)
+        .unwrap();
+
+    let filtros = config.filtros(&historico);
+    let livres = config.restricoes.livres();
+    let mut contagens = [0u64; 4];
+    for (i, a) in livres.iter().enumerate() {
+        for (j, b) in livres.iter().enumerate().skip(i + 1) {
+            for (k, c) in livres.iter().enumerate().skip(j + 1) {
+                for d in &livres[k + 1..] {
+                    let mut bolas = fixos.clone();
+                    bolas.extend([*a, *b, *c, *d]);
+                    let jogo = Bet::new(bolas).unwrap();
+                    contagens[0] += 1;
+                    let etapa = match filtros.avaliar(&jogo, &historico) {
+                        Err(Rejeicao::Soma { .. }) => 0,
+                        Err(Rejeicao::Paridade { .. }) => 1,
+                        Err(Rejeicao::SobreposicaoHistorico { .. }) => 2,
+                        Ok(()) => 3,
+                    };
+                    for contagem in &mut contagens[1..=etapa] {
+                        *contagem += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let obtidas: Vec<(&str, u64)> = enumeracao
+        .estagios
+        .iter()
+        .map(|e| (e.filtro, e.sobreviventes))
+        .collect();
+    assert_eq!(
+        obtidas,
+        vec![
+            ("restricoes", contagens[0]),
+            ("soma", contagens[1]),
+            ("paridade", contagens[2]),
+            ("sobreposicao_historico", contagens[3]),
+        ]
+    );
+    assert_eq!(enumeracao.amostra.len(), 5);
+    for jogo in &enumeracao.amostra {
+        assert_eq!(filtros.avaliar(jogo, &historico), Ok(()));
+        assert!(fixos.iter().all(|b| jogo.contains(*b)));
+    }
+}
+
+#[test]
+fn seeded_runs_repeat_with_any_number_of_threads() {
+    let dir = TempDir::new().unwrap();
+    let historico = store_com_historico(&dir)
+        .historico(&Periodo::todos())
+        .unwrap();
+    let config = GeneratorConfig {
+        quantidade: 20,
+        anti_popular: Some(3),
+        ..GeneratorConfig::default()
+    };
+    let enumeracao_config = GeneratorConfig {
+        restricoes: Restricoes {
+            fixos: vec![Ball::new(7).unwrap()],
+            excluidos: (40..=60).map(|n: u8| n.try_into().unwrap()).collect(),
+        },
+        ..GeneratorConfig::default()
+    };
+
+    let rodar = |threads: usize, semente: u64| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let mut rng = StdRng::seed_from_u64(semente);
+            let estimativa =
+                mega_sena_service::estimar_viabilidade(&historico, &config, &mut rng, 5_000)
+                    .unwrap();
+            let jogos =
+                mega_sena_service::generate_batch(&historico, &config, &mut rng, |_| {}).unwrap();
+            let enumeracao =
+                enumeracao::enumerar(&historico, &enumeracao_config, &mut rng, 8).unwrap();
+            (estimativa, jogos, enumeracao)
+        })
+    };
+
+    let um = rodar(1, 11);
+    assert_eq!(um, rodar(4, 11));
+    assert_eq!(um.1.len(), 20);
+    assert_eq!(um.2.amostra.len(), 8);
+    assert_ne!(um.1, rodar(4, 12).1);
+}
+
+#[test]
+fn history_scans_keep_draw_order_with_any_thread_count() {
+    // O CSV completo tem milhares de concursos: a varredura passa por vários
+    // blocos paralelos.
+    let mut store = Store::open_in_memory().unwrap();
+    store.import_csv("mega_sena.csv").unwrap();
+    let historico = store.historico(&Periodo::todos()).unwrap();
+    let aposta: Bet = "4 5 30 33 41 52".parse().unwrap();
+
+    let rodar = |threads: usize| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            (
+                analyser::coincidencias(&aposta, &historico, 3),
+                conferencia::conferir(&aposta, &historico, &HashMap::new(), &Rateio::default()),
+            )
+        })
+    };
+
+    let (coincidencias, conferencia) = rodar(4);
+    let sequencial: Vec<u32> = historico
+        .iter()
+        .filter(|d| aposta.acertos(d) >= 4)
+        .map(|d| d.concurso)
+        .collect();
+    let premiados: Vec<u32> = conferencia
+        .premiacoes
+        .iter()
+        .map(|p| p.concurso.concurso)
+        .collect();
+    assert_eq!(premiados, sequencial);
+    assert!(coincidencias.len() > sequencial.len());
+    assert!(
+        coincidencias
+            .windows(2)
+            .all(|par| par[0].concurso.concurso < par[1].concurso.concurso)
+    );
+    assert_eq!((coincidencias, conferencia), rodar(1));
+}
+
+#[test]
+fn optimized_batch_beats_random_batches_and_is_recorded() {
+    let dir = TempDir::new().unwrap();
+    let mut store = store_com_historico(&dir);
+    let historico = store.historico(&Periodo::todos()).unwrap();
+    let config = GeneratorConfig {
+        quantidade: 10,
+        otimizacao: Some(Otimizacao {
+            objetivo: Objetivo::CoberturaPares,
+            iteracoes: 5_000,
+            ..Otimizacao::default()
+        }),
+        ..GeneratorConfig::default()
+    };
+
+    let otimizado = store
+        .optimize(
+            &Periodo::todos(),
+            &config,
+            &mut StdRng::seed_from_u64(7),
+            |_| {},
+        )
+        .unwrap();
+
+    assert_eq!(otimizado.jogos.len(), 10);
+    assert!(otimizado.valor >= otimizado.inicial);
+    assert!(otimizado.valor > otimizado.aleatorio.unwrap());
+    // Dez jogos de seis números cobrem no máximo 10 × 15 pares.
+    assert!(otimizado.valor <= 150.0);
+    let filtros = config.filtros(&historico);
+    for (i, jogo) in otimizado.jogos.iter().enumerate() {
+        assert_eq!(filtros.avaliar(jogo, &historico), Ok(()));
+        assert!(!otimizado.jogos[i + 1..].contains(jogo));
+    }
+
+    let lote_id = store.save_batch(&otimizado.jogos, &config, None).unwrap();
+    let gravada: String = store
+        .connection()
+        .query_row(
+            "SELECT config FROM t_lote WHERE id = ?1",
+            [lote_id],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<GeneratorConfig>(&gravada).unwrap(),
+        config
+    );
+}
@@ -0,0 +1,96 @@
+use mega_da_virada_generator::SelecaoLotes;
+use mega_da_virada_generator::tui::{Aba, Controle, Painel};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+mod common;
+
+fn painel() -> Painel {
+    Painel::new(common::store_em_memoria(), StdRng::seed_from_u64(7)).unwrap()
+}
+
+fn teclar(painel: &mut Painel, teclas: &[KeyCode]) -> Controle {
+    let mut controle = Controle::Continua;
+    for &tecla in teclas {
+        controle = painel.tecla(KeyEvent::from(tecla));
+    }
+    controle
+}
+
+fn tela(painel: &mut Painel) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|frame| painel.desenhar(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content
+        .chunks(buffer.area.width as usize)
+        .map(|linha| linha.iter().map(|c| c.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn browses_history_and_stats() {
+    let mut painel = painel();
+
+    let historico = tela(&mut painel);
+    assert!(historico.contains("3 concursos"), "{}", historico);
+    let recente = historico.find("25/03/1996").unwrap();
+    assert!(recente < historico.find("11/03/1996").unwrap());
+
+    teclar(&mut painel, &[KeyCode::Char('2'), KeyCode::Char('o')]);
+    assert_eq!(painel.aba(), Aba::Estatisticas);
+    let estatisticas = tela(&mut painel);
+    assert!(
+        estatisticas.contains("mais sorteadas primeiro"),
+        "{}",
+        estatisticas
+    );
+    assert!(estatisticas.contains("soma entre"), "{}", estatisticas);
+
+    assert_eq!(teclar(&mut painel, &[KeyCode::Char('q')]), Controle::Sai);
+}
+
+#[test]
+fn regenerates_keeping_marked_games_and_saves_them() {
+    let mut painel = painel();
+    teclar(&mut painel, &[KeyCode::Tab, KeyCode::Tab]);
+    assert_eq!(painel.aba(), Aba::Gerador);
+    assert_eq!(painel.jogos().count(), painel.config().quantidade);
+
+    // Mudar um filtro refaz o lote na hora.
+    let antes: Vec<_> = painel.jogos().map(|(j, _)| j.clone()).collect();
+    teclar(&mut painel, &[KeyCode::Char('-'), KeyCode::Char('-')]);
+    assert_eq!(painel.jogos().count(), painel.config().quantidade);
+    assert_ne!(
+        painel.jogos().map(|(j, _)| j.clone()).collect::<Vec<_>>(),
+        antes
+    );
+
+    teclar(
+        &mut painel,
+        &[KeyCode::Char('s'), KeyCode::Down, KeyCode::Char(' ')],
+    );
+    assert!(painel.mensagem().unwrap().contains("marque"));
+    let guardado = painel.jogos().nth(1).map(|(j, _)| j.clone()).unwrap();
+    teclar(&mut painel, &[KeyCode::Char('e'), KeyCode::Char('g')]);
+    let marcados: Vec<_> = painel.jogos().filter(|(_, m)| *m).collect();
+    assert_eq!(marcados, vec![(&guardado, true)]);
+    assert_eq!(painel.jogos().count(), painel.config().quantidade);
+    assert!(tela(&mut painel).contains("1 marcados"));
+
+    teclar(&mut painel, &[KeyCode::Char('s')]);
+    assert!(
+        painel
+            .mensagem()
+            .unwrap()
+            .starts_with("Lote 1 gravado com 1 jogos")
+    );
+    assert_eq!(painel.jogos().filter(|(_, m)| *m).count(), 0);
+    let lote = painel.store().lotes(&SelecaoLotes::Ultimo).unwrap();
+    assert_eq!(lote[0].apostas[0].numeros, guardado);
+    assert_eq!(lote[0].strategy.as_deref(), Some("frequencia"));
+}
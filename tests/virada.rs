@@ -0,0 +1,132 @@
+use std::fs;
+
+use chrono::NaiveDate;
+use mega_da_virada_generator::engine::virada::{self, Acumulado, Contagem};
+use mega_da_virada_generator::{Ball, Draw, GeneratorConfig, SelecaoLotes, Store, api};
+use serde_json::json;
+use tempfile::TempDir;
+
+fn dia(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, mes, dia).unwrap()
+}
+
+fn sorteio(concurso: u32, data: NaiveDate) -> Draw {
+    let bolas = (1..=6).map(|b| Ball::new(b).unwrap()).collect();
+    Draw::new(concurso, data, bolas).unwrap()
+}
+
+#[test]
+fn viradas_follow_the_known_list_then_the_date_rule() {
+    assert!(virada::e_virada(&sorteio(2810, dia(2024, 12, 31))));
+    // Ano da lista: vale o concurso, não a data.
+    assert!(!virada::e_virada(&sorteio(2811, dia(2024, 12, 31))));
+    // Antes da primeira Virada, 31 de dezembro era um concurso comum.
+    assert!(!virada::e_virada(&sorteio(931, dia(2007, 12, 31))));
+    // Anos depois da lista seguem a regra da data.
+    assert!(virada::e_virada(&sorteio(3000, dia(2026, 12, 31))));
+    assert!(!virada::e_virada(&sorteio(2999, dia(2026, 12, 30))));
+
+    let contagem = Contagem::a_partir_de(dia(2026, 10, 19));
+    assert_eq!((contagem.ano, contagem.dias), (2026, 73));
+    assert_eq!(
+        contagem.to_string(),
+        "Mega da Virada 2026 (31/12/2026): faltam 73 dias"
+    );
+    assert_eq!(Contagem::a_partir_de(dia(2026, 12, 31)).dias, 0);
+    assert!(
+        Contagem::a_partir_de(dia(2026, 12, 30))
+            .to_string()
+            .ends_with("é amanhã")
+    );
+}
+
+#[test]
+fn panorama_restricts_stats_to_viradas_and_reports_accumulated_prizes() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("mega_sena.csv");
+    fs::write(
+        &csv_path,
+        "\
+concurso,data,bola_1,bola_2,bola_3,bola_4,bola_5,bola_6,rateio_sena,acumulado_sena,acumulado_virada
+2812,04/01/2025,1,2,3,4,5,6,,\"R$ 3.000.000,00\",\"R$ 25.000.000,00\"
+2811,02/01/2025,7,8,9,10,11,12,,,
+2810,31/12/2024,1,17,19,29,50,57,\"R$ 211.744.402,26\",,
+2809,28/12/2024,13,14,15,16,17,18,,,\"R$ 900.000.000,00\"
+2670,31/12/2023,21,24,33,41,48,56,,,
+",
+    )
+    .unwrap();
+    let mut store = Store::open(dir.path().join("mega_sena.db")).unwrap();
+    store.import_csv(&csv_path).unwrap();
+
+    let panorama = store.virada(dia(2025, 1, 5)).unwrap();
+    assert_eq!(panorama.proxima.ano, 2025);
+    let concursos: Vec<u32> = panorama
+        .viradas
+        .iter()
+        .map(|v| v.concurso.concurso)
+        .collect();
+    assert_eq!(concursos, vec![2670, 2810]);
+    assert_eq!(panorama.viradas[1].rateio_sena, Some(21_174_440_226));
+    assert_eq!(panorama.estatisticas.concursos, 2);
+    assert_eq!(panorama.estatisticas.frequencia[0], 1);
+    assert_eq!(panorama.estatisticas.frequencia[1], 0);
+    assert_eq!(
+        panorama.reserva,
+        Some(Acumulado {
+            concurso: 2812,
+            centavos: 2_500_000_000
+        })
+    );
+    assert_eq!(panorama.acumulado_sena.unwrap().centavos, 300_000_000);
+
+    // A reserva informada antes da última Virada já foi sorteada.
+    let conn = store.connection();
+    conn.execute(
+        "UPDATE t_mega_sena SET acumulado_virada_centavos = NULL WHERE concurso = 2812",
+        [],
+    )
+    .unwrap();
+    assert_eq!(store.virada(dia(2025, 1, 5)).unwrap().reserva, None);
+}
+
+#[test]
+fn batches_are_tagged_for_a_virada() {
+    let mut store = Store::open_in_memory().unwrap();
+    let config = GeneratorConfig::default();
+    let comum = store
+        .save_batch(&["1 2 3 4 5 6".parse().unwrap()], &config, None)
+        .unwrap();
+    let da_virada = store
+        .save_batch(&["7 8 9 10 11 12".parse().unwrap()], &config, Some(2020))
+        .unwrap();
+    let marcado_depois = store
+        .save_batch(&["13 14 15 16 17 18".parse().unwrap()], &config, None)
+        .unwrap();
+    store.marcar_virada(marcado_depois, 2020).unwrap();
+    assert!(store.marcar_virada(99, 2020).is_err());
+
+    let lotes = store.lotes(&SelecaoLotes::Virada(2020)).unwrap();
+    let marcados: Vec<_> = lotes.iter().map(|lote| (lote.id, lote.virada)).collect();
+    assert_eq!(
+        marcados,
+        [(da_virada, Some(2020)), (marcado_depois, Some(2020))]
+    );
+    let comum = store.lotes(&SelecaoLotes::Ids(vec![comum])).unwrap();
+    assert_eq!(comum[0].virada, None);
+
+    let pedido = json!({ "config": { "quantidade": 2 }, "seed": 1, "virada": true });
+    let gerado = api::atender(
+        &mut store,
+        "POST",
+        "/generate",
+        pedido.to_string().as_bytes(),
+    );
+    assert_eq!(gerado.status, 200, "{}", gerado.corpo);
+    let ano = gerado.corpo["virada"].as_i64().unwrap() as i32;
+    assert_eq!(store.lotes(&SelecaoLotes::Virada(ano)).unwrap().len(), 1);
+
+    let panorama = api::atender(&mut store, "GET", "/virada", b"");
+    assert_eq!(panorama.status, 200);
+    assert_eq!(panorama.corpo["proxima"]["ano"], ano);
+}